@@ -0,0 +1,62 @@
+//! Compares allocations and time for churn-heavy lists with and without a node pool.
+//!
+//! Run with `cargo bench --bench pool`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use dreamberd_array::List;
+
+/// Counts every allocation made by the program.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ELEMS: usize = 1_000;
+const ROUNDS: usize = 1_000;
+
+/// Fills the list and empties it again, `ROUNDS` times, with an insert in the middle each round.
+fn churn(mut list: List<u64>) -> (usize, Duration) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for round in 0..ROUNDS as u64 {
+        for elem in 0..ELEMS as u64 - 1 {
+            list.push(black_box(round + elem));
+        }
+        list.insert((ELEMS / 2) as f32 + 0.5, round);
+        while let Some(elem) = list.pop() {
+            black_box(elem);
+        }
+    }
+    let elapsed = start.elapsed();
+    (ALLOCATIONS.load(Ordering::Relaxed) - before, elapsed)
+}
+
+fn main() {
+    for (name, list) in [
+        ("List::new", List::new()),
+        ("List::with_capacity", List::with_capacity(ELEMS)),
+    ] {
+        let (allocations, elapsed) = churn(list);
+        println!(
+            "{:<20} {:>10} allocations {:>10.2?}",
+            name, allocations, elapsed
+        );
+    }
+}
@@ -0,0 +1,589 @@
+//! A list that many threads can push to and pop from at once, without locks.
+//!
+//! [`AtomicList`] is a Treiber stack: `push` and `pop` work at the head with a single compare-and-swap,
+//! just like [`List`] only ever changes its head.
+//!
+//! Popped nodes cannot be freed right away, since another thread may still be reading them.
+//! They are freed by a small epoch scheme instead: every operation pins the current epoch,
+//! the epoch only moves on once every pinned thread has seen it,
+//! and a node popped in epoch `e` is freed once the epoch reaches `e + 2`.
+//!
+//! `pop` moves the element out of its node as soon as the node is unlinked, and only the node waits to be freed.
+//! [`AtomicList::snapshot`] and [`AtomicList::peek`] clone elements instead of lending them out:
+//! while one is cloned, `pop` waits before it takes that element, and a clone that comes too late starts over.
+//!
+//! So `push` is lock-free, and so is `pop` while nobody clones.
+//! A `pop` that unlinks an element being cloned waits for that clone to finish, yielding its thread while it does,
+//! so a slow `Clone` or a descheduled reader holds it up.
+//! A `snapshot` starts over whenever an element it reached is popped, so under a steady stream of pops
+//! it may never finish.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//! use std::thread;
+//! use dreamberd_array::atomic::AtomicList;
+//!
+//! let list = Arc::new(AtomicList::new());
+//! let workers: Vec<_> = (0..4)
+//!     .map(|i| {
+//!         let list = Arc::clone(&list);
+//!         thread::spawn(move || list.push(i))
+//!     })
+//!     .collect();
+//! for worker in workers {
+//!     worker.join().unwrap();
+//! }
+//!
+//! let mut popped: Vec<i32> = std::iter::from_fn(|| list.pop()).collect();
+//! popped.sort();
+//! assert_eq!(popped, vec![0, 1, 2, 3]);
+//! ```
+
+use std::fmt;
+use std::hint;
+use std::iter::Rev;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::slice;
+use std::thread;
+use std::sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+use crate::List;
+
+/// How many nodes are popped between attempts to free old ones.
+const COLLECT_EVERY: usize = 32;
+
+/// Set in a node's `state` once `pop` is about to take its element.
+const TAKEN: usize = 1;
+/// Added to a node's `state` for every thread cloning its element.
+const READER: usize = 2;
+
+/// How often `pop` spins on a clone in progress before it yields its thread instead.
+const SPINS: u32 = 64;
+
+struct Node<T> {
+    /// Moved out by `pop` or dropped with the list, never by freeing the node.
+    elem: ManuallyDrop<T>,
+    /// [`TAKEN`] plus [`READER`] for every clone in progress.
+    state: AtomicUsize,
+    /// Set before the node is pushed and never changed afterwards.
+    next: *mut Node<T>,
+    /// The next node waiting to be freed, once this one was popped.
+    retired_next: AtomicPtr<Node<T>>,
+    /// The epoch this node was popped in.
+    retired_epoch: AtomicUsize,
+}
+
+/// A slot a thread holds while it works on the list.
+struct Participant {
+    /// `0` when unpinned, otherwise the pinned epoch shifted left by one, with the lowest bit set.
+    epoch: AtomicUsize,
+    in_use: AtomicBool,
+    /// Set before the participant is published and never changed afterwards.
+    next: *mut Participant,
+}
+
+/// Releases a node's element for `pop` again when a clone is done, even if it panicked.
+struct Reading<'a>(&'a AtomicUsize);
+
+impl Drop for Reading<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(READER, Ordering::Release);
+    }
+}
+
+/// Keeps the list's current epoch pinned, so nodes seen under it are not freed.
+struct Guard<'a> {
+    participant: &'a Participant,
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.participant.epoch.store(0, Ordering::Release);
+        self.participant.in_use.store(false, Ordering::Release);
+    }
+}
+
+/// A stack of `T` that threads share without locks, indexed like a [`List`].
+pub struct AtomicList<T> {
+    head: AtomicPtr<Node<T>>,
+    epoch: AtomicUsize,
+    participants: AtomicPtr<Participant>,
+    /// Popped nodes waiting to be freed.
+    garbage: AtomicPtr<Node<T>>,
+    retired: AtomicUsize,
+    /// The list owns its nodes, but is only `Send` and `Sync` under the bounds below.
+    marker: PhantomData<*mut Node<T>>,
+}
+
+// Sending the whole list only moves the elements.
+unsafe impl<T: Send> Send for AtomicList<T> {}
+// Sharing it lets other threads read elements, and drop them when freeing nodes.
+unsafe impl<T: Send + Sync> Sync for AtomicList<T> {}
+
+impl<T> AtomicList<T> {
+    /// Creates an empty list.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::atomic::AtomicList;
+    ///
+    /// let list: AtomicList<i32> = AtomicList::new();
+    ///
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        AtomicList {
+            head: AtomicPtr::new(ptr::null_mut()),
+            epoch: AtomicUsize::new(0),
+            participants: AtomicPtr::new(ptr::null_mut()),
+            garbage: AtomicPtr::new(ptr::null_mut()),
+            retired: AtomicUsize::new(0),
+            marker: PhantomData,
+        }
+    }
+
+    /// Pushes an element to the front of the list, like [`List::push`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::atomic::AtomicList;
+    ///
+    /// let list = AtomicList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.peek(), Some(2));
+    /// ```
+    pub fn push(&self, elem: T) {
+        let node = Box::into_raw(Box::new(Node {
+            elem: ManuallyDrop::new(elem),
+            state: AtomicUsize::new(0),
+            next: ptr::null_mut(),
+            retired_next: AtomicPtr::new(ptr::null_mut()),
+            retired_epoch: AtomicUsize::new(0),
+        }));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the node is not published yet, so nothing else can see it.
+            unsafe { (*node).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Returns `true` if the list is empty right now.
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    /// Moves the elements into a [`List`], keeping their indexes.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::atomic::AtomicList;
+    /// use dreamberd_array::dlist;
+    ///
+    /// let list = AtomicList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.into_list(), dlist![1, 2]);
+    /// ```
+    pub fn into_list(mut self) -> List<T> {
+        let mut elems = Vec::new();
+        let mut cur = std::mem::replace(self.head.get_mut(), ptr::null_mut());
+        while !cur.is_null() {
+            // SAFETY: the list is owned, so no other thread can see its nodes.
+            let node = unsafe { Box::from_raw(cur) };
+            cur = node.next;
+            elems.push(ManuallyDrop::into_inner(node.elem));
+        }
+        elems.reverse();
+        List::from(elems)
+    }
+
+    /// Pops the element at the front of the list, like [`List::pop`].
+    ///
+    /// If another thread is cloning the popped element in [`AtomicList::peek`] or [`AtomicList::snapshot`],
+    /// this waits until that clone is done.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::atomic::AtomicList;
+    ///
+    /// let list = AtomicList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.pop(), Some(2));
+    /// assert_eq!(list.pop(), Some(1));
+    /// assert_eq!(list.pop(), None);
+    /// ```
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.pin();
+        let mut head = self.head.load(Ordering::Acquire);
+        let popped = loop {
+            if head.is_null() {
+                return None;
+            }
+            // SAFETY: the epoch is pinned, so `head` is not freed even if another thread pops it.
+            let next = unsafe { (*head).next };
+            match self
+                .head
+                .compare_exchange_weak(head, next, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => break head,
+                Err(current) => head = current,
+            }
+        };
+        // SAFETY: as above, the node stays alive while the epoch is pinned.
+        let node = unsafe { &*popped };
+        // No new clone can start once `TAKEN` is set, so wait for the ones already running.
+        node.state.fetch_or(TAKEN, Ordering::AcqRel);
+        let mut spins = 0;
+        while node.state.load(Ordering::Acquire) != TAKEN {
+            if spins < SPINS {
+                spins += 1;
+                hint::spin_loop();
+            } else {
+                thread::yield_now();
+            }
+        }
+        // SAFETY: winning the compare-and-swap made this thread the only one to take the element,
+        // and nothing reads it any more. Freeing the node later leaves the element alone.
+        let elem = unsafe { ptr::read(&*node.elem) };
+        drop(guard);
+        self.retire(popped);
+        Some(elem)
+    }
+
+    /// Runs `f` on a node's element unless `pop` has taken it, keeping `pop` from taking it meanwhile.
+    fn read<R>(node: &Node<T>, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let reading = Reading(&node.state);
+        if node.state.fetch_add(READER, Ordering::Acquire) & TAKEN != 0 {
+            return None;
+        }
+        let out = f(&node.elem);
+        drop(reading);
+        Some(out)
+    }
+
+    /// Pins the current epoch for the calling thread.
+    fn pin(&self) -> Guard<'_> {
+        let participant = self.participant();
+        loop {
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            participant.epoch.store(epoch << 1 | 1, Ordering::SeqCst);
+            fence(Ordering::SeqCst);
+            if self.epoch.load(Ordering::SeqCst) == epoch {
+                return Guard { participant };
+            }
+        }
+    }
+
+    /// Claims a free participant slot, adding one if all are taken.
+    fn participant(&self) -> &Participant {
+        let mut cur = self.participants.load(Ordering::Acquire);
+        while !cur.is_null() {
+            // SAFETY: participants are only freed when the list is dropped.
+            let participant = unsafe { &*cur };
+            if participant
+                .in_use
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                return participant;
+            }
+            cur = participant.next;
+        }
+        let new = Box::into_raw(Box::new(Participant {
+            epoch: AtomicUsize::new(0),
+            in_use: AtomicBool::new(true),
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.participants.load(Ordering::Acquire);
+        loop {
+            // SAFETY: the participant is not published yet.
+            unsafe { (*new).next = head };
+            match self
+                .participants
+                .compare_exchange_weak(head, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                // SAFETY: participants are only freed when the list is dropped.
+                Ok(_) => return unsafe { &*new },
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Moves the epoch on if every pinned participant has seen it, and returns the current epoch.
+    fn try_advance(&self) -> usize {
+        let epoch = self.epoch.load(Ordering::SeqCst);
+        fence(Ordering::SeqCst);
+        let mut cur = self.participants.load(Ordering::Acquire);
+        while !cur.is_null() {
+            // SAFETY: participants are only freed when the list is dropped.
+            let participant = unsafe { &*cur };
+            let local = participant.epoch.load(Ordering::SeqCst);
+            if local & 1 == 1 && local >> 1 != epoch {
+                return epoch;
+            }
+            cur = participant.next;
+        }
+        match self
+            .epoch
+            .compare_exchange(epoch, epoch + 1, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => epoch + 1,
+            Err(current) => current,
+        }
+    }
+
+    /// Queues a popped node to be freed.
+    fn retire(&self, node: *mut Node<T>) {
+        // SAFETY: the node was popped by the caller, so only its retire fields are written here.
+        let node_ref = unsafe { &*node };
+        node_ref
+            .retired_epoch
+            .store(self.epoch.load(Ordering::SeqCst), Ordering::Relaxed);
+        self.push_garbage(node);
+        if self.retired.fetch_add(1, Ordering::Relaxed) % COLLECT_EVERY == COLLECT_EVERY - 1 {
+            self.collect();
+        }
+    }
+
+    fn push_garbage(&self, node: *mut Node<T>) {
+        // SAFETY: the node is retired and not freed before it is taken off the garbage list.
+        let node_ref = unsafe { &*node };
+        let mut head = self.garbage.load(Ordering::Relaxed);
+        loop {
+            node_ref.retired_next.store(head, Ordering::Relaxed);
+            match self
+                .garbage
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Frees the popped nodes no pinned thread can still see.
+    fn collect(&self) {
+        let epoch = self.try_advance();
+        let mut cur = self.garbage.swap(ptr::null_mut(), Ordering::Acquire);
+        while !cur.is_null() {
+            let node = cur;
+            // SAFETY: the swap made this thread the only one holding these garbage nodes.
+            let (next, retired_epoch) = unsafe {
+                (
+                    (*node).retired_next.load(Ordering::Relaxed),
+                    (*node).retired_epoch.load(Ordering::Relaxed),
+                )
+            };
+            cur = next;
+            if retired_epoch + 2 <= epoch {
+                // SAFETY: every thread that could have seen the node has unpinned since it was popped.
+                drop(unsafe { Box::from_raw(node) });
+            } else {
+                self.push_garbage(node);
+            }
+        }
+    }
+}
+
+impl<T: Clone> AtomicList<T> {
+    /// Returns a clone of the element at the front of the list.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::atomic::AtomicList;
+    ///
+    /// let list = AtomicList::new();
+    /// assert_eq!(list.peek(), None);
+    ///
+    /// list.push(1);
+    /// assert_eq!(list.peek(), Some(1));
+    /// ```
+    pub fn peek(&self) -> Option<T> {
+        let _guard = self.pin();
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            if head.is_null() {
+                return None;
+            }
+            // SAFETY: the epoch is pinned, so `head` is not freed even if another thread pops it.
+            if let Some(elem) = Self::read(unsafe { &*head }, T::clone) {
+                return Some(elem);
+            }
+        }
+    }
+
+    /// Clones the whole list as it is at one moment.
+    ///
+    /// Pushes and pops by other threads while the snapshot is taken do not mix into it.
+    /// When an element is popped before it was cloned, the snapshot starts over,
+    /// so it may not finish while other threads keep popping.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::atomic::AtomicList;
+    ///
+    /// let list = AtomicList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// let snapshot = list.snapshot();
+    /// list.pop();
+    /// list.push(3);
+    ///
+    /// assert_eq!(snapshot.iter().collect::<Vec<_>>(), vec![&2, &1]);
+    /// assert_eq!(snapshot.get(-1.0), Some(&1));
+    /// ```
+    pub fn snapshot(&self) -> Snapshot<T> {
+        let _guard = self.pin();
+        'retry: loop {
+            let mut elems = Vec::new();
+            let mut cur = self.head.load(Ordering::Acquire);
+            while !cur.is_null() {
+                // SAFETY: the epoch is pinned, and a node's `next` never changes.
+                let node = unsafe { &*cur };
+                match Self::read(node, T::clone) {
+                    Some(elem) => elems.push(elem),
+                    // Popped since the head was read, so the rest may not match it any more.
+                    None => continue 'retry,
+                }
+                cur = node.next;
+            }
+            elems.reverse();
+            return Snapshot { elems };
+        }
+    }
+}
+
+impl<T> Drop for AtomicList<T> {
+    fn drop(&mut self) {
+        let mut cur = std::mem::replace(self.head.get_mut(), ptr::null_mut());
+        while !cur.is_null() {
+            // SAFETY: the list is owned, so no other thread can see its nodes.
+            let mut node = unsafe { Box::from_raw(cur) };
+            cur = node.next;
+            // SAFETY: the node is still in the list, so its element was never taken.
+            unsafe { ManuallyDrop::drop(&mut node.elem) };
+        }
+        // Popped nodes have had their elements taken already.
+        let mut cur = std::mem::replace(self.garbage.get_mut(), ptr::null_mut());
+        while !cur.is_null() {
+            // SAFETY: as above.
+            let node = unsafe { Box::from_raw(cur) };
+            cur = node.retired_next.load(Ordering::Relaxed);
+        }
+        let mut cur = std::mem::replace(self.participants.get_mut(), ptr::null_mut());
+        while !cur.is_null() {
+            // SAFETY: as above.
+            let participant = unsafe { Box::from_raw(cur) };
+            cur = participant.next;
+        }
+    }
+}
+
+impl<T> Default for AtomicList<T> {
+    fn default() -> Self {
+        AtomicList::new()
+    }
+}
+
+impl<T> FromIterator<T> for AtomicList<T> {
+    /// Pushes the elements in order, so the first one ends up at index -1.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let list = AtomicList::new();
+        for elem in iter {
+            list.push(elem);
+        }
+        list
+    }
+}
+
+impl<T> From<List<T>> for AtomicList<T> {
+    fn from(list: List<T>) -> Self {
+        Vec::from(list).into_iter().collect()
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for AtomicList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.snapshot(), f)
+    }
+}
+
+/// A copy of an [`AtomicList`] at one moment, returned by [`AtomicList::snapshot`].
+pub struct Snapshot<T> {
+    /// From index -1 up.
+    elems: Vec<T>,
+}
+
+impl<T> Snapshot<T> {
+    /// Returns the element at the front of the list.
+    pub fn peek(&self) -> Option<&T> {
+        self.elems.last()
+    }
+
+    /// Returns an iterator from the front of the list, like [`List::iter`].
+    pub fn iter(&self) -> SnapshotIter<'_, T> {
+        SnapshotIter {
+            inner: self.elems.iter().rev(),
+        }
+    }
+
+    /// Returns the number of elements in the snapshot.
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    /// Returns `true` if the snapshot is empty.
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Returns the element at an index, like [`List::get`].
+    pub fn get(&self, index: f32) -> Option<&T> {
+        self.elems.get(crate::position(index, self.elems.len())?)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Snapshot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.elems).finish()
+    }
+}
+
+impl<T> From<Snapshot<T>> for List<T> {
+    fn from(snapshot: Snapshot<T>) -> Self {
+        List::from(snapshot.elems)
+    }
+}
+
+/// An iterator over a [`Snapshot`], returned by [`Snapshot::iter`].
+pub struct SnapshotIter<'a, T> {
+    inner: Rev<slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for SnapshotIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
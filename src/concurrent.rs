@@ -0,0 +1,378 @@
+//! A list many threads can index into at once.
+//!
+//! [`ConcurrentList`] keeps a lock on every link between nodes, and walks the list hand over hand:
+//! it locks the next link before letting go of the previous one.
+//! Readers take read locks, so any number of them can pass the same node.
+//! Writers write-lock the links on their way, so no later call can overtake them,
+//! and only hold on to the one or two links they change.
+//!
+//! Like [`List`], the head holds the highest index, so `push` and `pop` only lock the head.
+//! The head lock also guards the length, which every call resolves its index against
+//! and updates before letting go of the head.
+//! Since nothing overtakes a write further down, calls take effect in the order they locked the head,
+//! which makes every one of them linearizable.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//! use std::thread;
+//! use dreamberd_array::concurrent::ConcurrentList;
+//! use dreamberd_array::dlist;
+//!
+//! let list = Arc::new(ConcurrentList::from(dlist![1, 2, 3]));
+//! let writer = {
+//!     let list = Arc::clone(&list);
+//!     thread::spawn(move || list.insert(0.5, 42))
+//! };
+//! let reader = {
+//!     let list = Arc::clone(&list);
+//!     thread::spawn(move || list.get(-1.0))
+//! };
+//! writer.join().unwrap();
+//!
+//! assert_eq!(reader.join().unwrap(), Some(1));
+//! assert_eq!(list.to_list(), dlist![1, 2, 42, 3]);
+//! ```
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::List;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    /// The link to the node with the next lower index.
+    next: RwLock<Link<T>>,
+}
+
+struct Head<T> {
+    /// The link to the node with the highest index.
+    link: Link<T>,
+    len: usize,
+}
+
+/// A thread-safe list with fine-grained locking, indexed like a [`List`].
+pub struct ConcurrentList<T> {
+    head: RwLock<Head<T>>,
+}
+
+// A panic never leaves a link half changed, so a poisoned lock is still usable.
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the node behind a locked link, for as long as the list is borrowed.
+///
+/// # Safety
+/// A node is only freed by `remove` or `pop`, which hold write locks on both the link into it
+/// and its own `next` link. The caller must keep one of those two locked while using the node.
+unsafe fn node<'a, T>(link: &Link<T>) -> Option<&'a Node<T>> {
+    link.as_deref().map(|node| &*(node as *const Node<T>))
+}
+
+/// Locks the link `distance` links below the head link with `lock`, and every link on the way there, hand over hand.
+///
+/// `distance` must be at least 1, and `head` is let go of as soon as the first link is locked.
+/// Writers pass `write`, so that no later call can overtake them while they are on their way.
+fn lock_below<'a, T, H, G>(head: H, distance: usize, lock: fn(&'a RwLock<Link<T>>) -> G) -> Option<G>
+where
+    H: Deref<Target = Head<T>>,
+    G: Deref<Target = Link<T>>,
+{
+    // SAFETY: `head` stays locked until the next link is.
+    let mut node = unsafe { node(&head.link)? };
+    let mut guard = lock(&node.next);
+    drop(head);
+    for _ in 1..distance {
+        // SAFETY: `guard` stays locked until the next link is.
+        node = unsafe { self::node(&guard)? };
+        guard = lock(&node.next);
+    }
+    Some(guard)
+}
+
+/// Takes the node behind a write-locked link out of the list and returns its element.
+fn unlink<T>(link: &mut Link<T>) -> Option<T> {
+    // SAFETY: `link` stays locked until the node is unlinked.
+    let node = unsafe { node(link)? };
+    let rest = write(&node.next).take();
+    let removed = std::mem::replace(link, rest)?;
+    Some(removed.elem)
+}
+
+impl<T> ConcurrentList<T> {
+    /// Creates an empty list.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::concurrent::ConcurrentList;
+    ///
+    /// let list: ConcurrentList<i32> = ConcurrentList::new();
+    ///
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        ConcurrentList {
+            head: RwLock::new(Head { link: None, len: 0 }),
+        }
+    }
+
+    /// Pushes an element after the highest index, like [`List::push`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::concurrent::ConcurrentList;
+    ///
+    /// let list = ConcurrentList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.get(0.0), Some(2));
+    /// ```
+    pub fn push(&self, elem: T) {
+        let mut head = write(&self.head);
+        let next = head.link.take();
+        head.link = Some(Box::new(Node {
+            elem,
+            next: RwLock::new(next),
+        }));
+        head.len += 1;
+    }
+
+    /// Pops the element with the highest index, like [`List::pop`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::concurrent::ConcurrentList;
+    ///
+    /// let list = ConcurrentList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.pop(), Some(2));
+    /// assert_eq!(list.pop(), Some(1));
+    /// assert_eq!(list.pop(), None);
+    /// ```
+    pub fn pop(&self) -> Option<T> {
+        let mut head = write(&self.head);
+        let elem = unlink(&mut head.link)?;
+        head.len -= 1;
+        Some(elem)
+    }
+
+    /// Inserts an element at an index, like [`List::insert`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::concurrent::ConcurrentList;
+    /// use dreamberd_array::dlist;
+    ///
+    /// let list = ConcurrentList::from(dlist![1, 2]);
+    /// list.insert(-0.5, 42);
+    /// list.insert(5.0, 0);
+    ///
+    /// assert_eq!(list.to_list(), dlist![1, 42, 2]);
+    /// ```
+    pub fn insert(&self, index: f32, elem: T) {
+        let mut head = write(&self.head);
+        let Some(position) = crate::position(index, head.len) else {
+            return;
+        };
+        // The new node goes right below the one at `position`, which moves up.
+        let distance = head.len - position;
+        head.len += 1;
+        let Some(mut guard) = lock_below(head, distance, write) else {
+            return;
+        };
+        let next = guard.take();
+        *guard = Some(Box::new(Node {
+            elem,
+            next: RwLock::new(next),
+        }));
+    }
+
+    /// Removes the element at an index, like [`List::remove`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::concurrent::ConcurrentList;
+    /// use dreamberd_array::dlist;
+    ///
+    /// let list = ConcurrentList::from(dlist![1, 2, 3]);
+    ///
+    /// assert_eq!(list.remove(-0.5), Some(2));
+    /// assert_eq!(list.remove(1.0), None);
+    /// assert_eq!(list.to_list(), dlist![1, 3]);
+    /// ```
+    pub fn remove(&self, index: f32) -> Option<T> {
+        let mut head = write(&self.head);
+        let position = crate::position(index, head.len)?;
+        let distance = head.len - 1 - position;
+        head.len -= 1;
+        if distance == 0 {
+            return unlink(&mut head.link);
+        }
+        unlink(&mut *lock_below(head, distance, write)?)
+    }
+
+    /// Returns the number of elements in the list right now.
+    pub fn len(&self) -> usize {
+        read(&self.head).len
+    }
+
+    /// Returns `true` if the list is empty right now.
+    pub fn is_empty(&self) -> bool {
+        read(&self.head).len == 0
+    }
+
+    /// Calls `f` with the elements from index -1 up, all read-locked as one consistent view of the list.
+    fn with_elems<R>(&self, f: impl FnOnce(Vec<&T>) -> R) -> R {
+        let head = read(&self.head);
+        let mut elems = Vec::with_capacity(head.len);
+        let mut guards = Vec::with_capacity(head.len);
+        // SAFETY: every link stays locked until `f` returns.
+        let mut cur = unsafe { node(&head.link) };
+        while let Some(node_ref) = cur {
+            elems.push(&node_ref.elem);
+            let guard = read(&node_ref.next);
+            // SAFETY: as above.
+            cur = unsafe { node(&guard) };
+            guards.push(guard);
+        }
+        elems.reverse();
+        f(elems)
+    }
+
+    /// Calls `f` on every element from index -1 up.
+    ///
+    /// The whole list is read-locked while `f` runs,
+    /// so `f` must not change this list or it will deadlock.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::concurrent::ConcurrentList;
+    /// use dreamberd_array::dlist;
+    ///
+    /// let list = ConcurrentList::from(dlist![1, 2, 3]);
+    /// let mut sum = 0;
+    /// list.for_each(|x| sum += x);
+    ///
+    /// assert_eq!(sum, 6);
+    /// ```
+    pub fn for_each<F: FnMut(&T)>(&self, f: F) {
+        self.with_elems(|elems| elems.into_iter().for_each(f));
+    }
+
+    /// Moves the elements into a [`List`], keeping their indexes.
+    pub fn into_list(mut self) -> List<T> {
+        let mut elems = Vec::new();
+        let mut cur = self
+            .head
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .link
+            .take();
+        while let Some(node) = cur {
+            let node = *node;
+            elems.push(node.elem);
+            cur = node
+                .next
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        elems.reverse();
+        List::from(elems)
+    }
+}
+
+impl<T: Clone> ConcurrentList<T> {
+    /// Returns a clone of the element at an index, like [`List::get`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::concurrent::ConcurrentList;
+    /// use dreamberd_array::dlist;
+    ///
+    /// let list = ConcurrentList::from(dlist![1, 2, 3]);
+    ///
+    /// assert_eq!(list.get(-1.0), Some(1));
+    /// assert_eq!(list.get(0.5), Some(3));
+    /// assert_eq!(list.get(2.0), None);
+    /// ```
+    pub fn get(&self, index: f32) -> Option<T> {
+        let head = read(&self.head);
+        let position = crate::position(index, head.len)?;
+        let distance = head.len - 1 - position;
+        if distance == 0 {
+            return head.link.as_ref().map(|node| node.elem.clone());
+        }
+        let guard = lock_below(head, distance, read)?;
+        guard.as_ref().map(|node| node.elem.clone())
+    }
+
+    /// Copies the elements into a [`List`], as one consistent view of the list.
+    pub fn to_list(&self) -> List<T> {
+        self.with_elems(|elems| elems.into_iter().cloned().collect())
+    }
+}
+
+impl<T> Drop for ConcurrentList<T> {
+    fn drop(&mut self) {
+        let mut cur = self
+            .head
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .link
+            .take();
+        while let Some(mut node) = cur {
+            cur = node
+                .next
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take();
+        }
+    }
+}
+
+impl<T> Default for ConcurrentList<T> {
+    fn default() -> Self {
+        ConcurrentList::new()
+    }
+}
+
+impl<T> From<List<T>> for ConcurrentList<T> {
+    fn from(list: List<T>) -> Self {
+        let len = list.len();
+        // Link the nodes from index -1 up, so the last one ends up at the head.
+        let mut link = None;
+        for elem in Vec::from(list) {
+            link = Some(Box::new(Node {
+                elem,
+                next: RwLock::new(link),
+            }));
+        }
+        ConcurrentList {
+            head: RwLock::new(Head { link, len }),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ConcurrentList<T> {
+    /// Pushes the elements in order, so the first one ends up at index -1.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        ConcurrentList::from(iter.into_iter().collect::<List<T>>())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ConcurrentList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with_elems(|elems| f.debug_list().entries(elems).finish())
+    }
+}
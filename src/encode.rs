@@ -0,0 +1,415 @@
+//! A small binary encoding for `List`, without any dependencies.
+//!
+//! Use [`to_bytes`] or [`to_bytes_with_keys`] to write a list and [`from_bytes`] to read it back.
+//! Element types implement [`Encode`] and [`Decode`], which come implemented for the primitives,
+//! `String`, `Vec`, tuples and `List` itself, so lists can be nested.
+//!
+//! # Format
+//! All numbers are little endian.
+//!
+//! | Bytes  | Content                                                          |
+//! |--------|------------------------------------------------------------------|
+//! | 4      | Magic, `DBAR`                                                    |
+//! | 1      | Version, currently `1`                                           |
+//! | 1      | Flags, `1` if every element is preceded by its index as an `f32` |
+//! | 8      | Length of the body in bytes as a `u64`                           |
+//! | 8      | Body: number of elements as a `u64`                              |
+//! | ...    | Body: the elements, starting at index -1                         |
+//! | 4      | FNV-1a checksum over the body                                    |
+//!
+//! Nested lists, `Vec`s and `String`s are written as a `u64` length followed by their contents.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::{dlist, encode, List};
+//!
+//! let list = dlist![(1u8, "one".to_string()), (2u8, "two".to_string())];
+//! let bytes = encode::to_bytes(&list);
+//!
+//! let back: List<(u8, String)> = encode::from_bytes(&bytes).unwrap();
+//! assert_eq!(back, list);
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::List;
+
+const MAGIC: &[u8; 4] = b"DBAR";
+const VERSION: u8 = 1;
+const FLAG_KEYS: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 2 + 8;
+const CHECKSUM_LEN: usize = 4;
+
+/// The ways decoding can fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// The input ended before the value was complete.
+    UnexpectedEnd,
+    /// The input does not start with the `DBAR` magic.
+    BadMagic,
+    /// The input was written by an unknown version of the format.
+    UnsupportedVersion(u8),
+    /// The header contains unknown flags.
+    UnknownFlags(u8),
+    /// The checksum does not match the contents.
+    ChecksumMismatch,
+    /// A `bool` was neither `0` nor `1`.
+    InvalidBool(u8),
+    /// A `char` was not a valid unicode scalar value.
+    InvalidChar(u32),
+    /// A `String` was not valid UTF-8.
+    InvalidUtf8,
+    /// A stored index was not finite or appeared twice.
+    InvalidKey(f32),
+    /// A stored integer does not fit the target type.
+    IntegerOverflow(u64),
+    /// There were bytes left after the value was complete.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodeError::BadMagic => write!(f, "input is not an encoded list"),
+            DecodeError::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            DecodeError::UnknownFlags(flags) => write!(f, "unknown flags {:#04x}", flags),
+            DecodeError::ChecksumMismatch => write!(f, "checksum mismatch"),
+            DecodeError::InvalidBool(byte) => write!(f, "invalid bool {}", byte),
+            DecodeError::InvalidChar(value) => write!(f, "invalid char {:#x}", value),
+            DecodeError::InvalidUtf8 => write!(f, "invalid utf-8 in string"),
+            DecodeError::InvalidKey(key) => write!(f, "invalid index {}", key),
+            DecodeError::IntegerOverflow(value) => write!(f, "integer {} out of range", value),
+            DecodeError::TrailingBytes(count) => write!(f, "{} trailing bytes", count),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// A value that can be written in the binary format.
+pub trait Encode {
+    /// Appends the encoded value to `out`.
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// A value that can be read from the binary format.
+pub trait Decode: Sized {
+    /// Reads a value from the front of `input` and advances it past the value.
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError>;
+}
+
+/// Encodes a list with a header and checksum.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::{dlist, encode};
+///
+/// let bytes = encode::to_bytes(&dlist![1u8, 2u8]);
+/// assert_eq!(&bytes[..4], b"DBAR");
+/// ```
+pub fn to_bytes<T: Encode>(list: &List<T>) -> Vec<u8> {
+    write(list, 0)
+}
+
+/// Encodes a list with a header and checksum, storing the index of every element.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::{dlist, encode, List};
+///
+/// let list = dlist![1u8, 2u8];
+/// let bytes = encode::to_bytes_with_keys(&list);
+///
+/// let back: List<u8> = encode::from_bytes(&bytes).unwrap();
+/// assert_eq!(back, list);
+/// ```
+pub fn to_bytes_with_keys<T: Encode>(list: &List<T>) -> Vec<u8> {
+    write(list, FLAG_KEYS)
+}
+
+/// Decodes a list written by [`to_bytes`] or [`to_bytes_with_keys`].
+///
+/// Stored indexes may be fractional. The elements are ordered by their index,
+/// and the resulting list is indexed from -1 again.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::encode::{self, DecodeError};
+/// use dreamberd_array::{dlist, List};
+///
+/// let bytes = encode::to_bytes(&dlist![1u32, 2u32]);
+///
+/// assert_eq!(encode::from_bytes::<u32>(&bytes[..10]), Err(DecodeError::UnexpectedEnd));
+/// assert_eq!(encode::from_bytes::<u32>(b"nope"), Err(DecodeError::BadMagic));
+/// ```
+pub fn from_bytes<T: Decode>(bytes: &[u8]) -> Result<List<T>, DecodeError> {
+    if bytes.len() < MAGIC.len() {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    if &bytes[..MAGIC.len()] != MAGIC {
+        return Err(DecodeError::BadMagic);
+    }
+    if bytes.len() < HEADER_LEN {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let version = bytes[MAGIC.len()];
+    if version != VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+    let flags = bytes[MAGIC.len() + 1];
+    if flags & !FLAG_KEYS != 0 {
+        return Err(DecodeError::UnknownFlags(flags));
+    }
+
+    let mut rest = &bytes[MAGIC.len() + 2..];
+    let body_len = read_len(&mut rest)?;
+    let body = take(&mut rest, body_len)?;
+    let checksum = take(&mut rest, CHECKSUM_LEN)?;
+    if !rest.is_empty() {
+        return Err(DecodeError::TrailingBytes(rest.len()));
+    }
+    if checksum != fnv1a(body).to_le_bytes() {
+        return Err(DecodeError::ChecksumMismatch);
+    }
+
+    let mut input = body;
+    let list = if flags & FLAG_KEYS != 0 {
+        read_keyed(&mut input)?
+    } else {
+        List::decode(&mut input)?
+    };
+    if !input.is_empty() {
+        return Err(DecodeError::TrailingBytes(input.len()));
+    }
+    Ok(list)
+}
+
+fn write<T: Encode>(list: &List<T>, flags: u8) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.push(flags);
+    out.extend_from_slice(&[0; 8]);
+
+    let elems = list.index_order();
+    (elems.len() as u64).encode(&mut out);
+    for (i, elem) in elems.enumerate() {
+        if flags & FLAG_KEYS != 0 {
+            (i as f32 - 1.0).encode(&mut out);
+        }
+        elem.encode(&mut out);
+    }
+
+    let body_len = (out.len() - HEADER_LEN) as u64;
+    out[HEADER_LEN - 8..HEADER_LEN].copy_from_slice(&body_len.to_le_bytes());
+    let checksum = fnv1a(&out[HEADER_LEN..]);
+    out.extend_from_slice(&checksum.to_le_bytes());
+    out
+}
+
+fn read_keyed<T: Decode>(input: &mut &[u8]) -> Result<List<T>, DecodeError> {
+    let len = read_len(input)?;
+    let mut entries: Vec<(f32, T)> = Vec::new();
+    for _ in 0..len {
+        let key = f32::decode(input)?;
+        if !key.is_finite() {
+            return Err(DecodeError::InvalidKey(key));
+        }
+        entries.push((key, T::decode(input)?));
+    }
+    entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+    if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        return Err(DecodeError::InvalidKey(pair[0].0));
+    }
+    Ok(entries.into_iter().map(|(_, elem)| elem).collect())
+}
+
+fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in bytes {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+fn take<'a>(input: &mut &'a [u8], count: usize) -> Result<&'a [u8], DecodeError> {
+    if input.len() < count {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let (front, rest) = input.split_at(count);
+    *input = rest;
+    Ok(front)
+}
+
+fn read_len(input: &mut &[u8]) -> Result<usize, DecodeError> {
+    let len = u64::decode(input)?;
+    usize::try_from(len).map_err(|_| DecodeError::IntegerOverflow(len))
+}
+
+macro_rules! impl_number {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                    let bytes = take(input, core::mem::size_of::<$ty>())?;
+                    Ok(<$ty>::from_le_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl Encode for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out);
+    }
+}
+
+impl Decode for usize {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        read_len(input)
+    }
+}
+
+impl Encode for isize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as i64).encode(out);
+    }
+}
+
+impl Decode for isize {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let value = i64::decode(input)?;
+        isize::try_from(value).map_err(|_| DecodeError::IntegerOverflow(value as u64))
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+impl Decode for bool {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => Err(DecodeError::InvalidBool(byte)),
+        }
+    }
+}
+
+impl Encode for char {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u32).encode(out);
+    }
+}
+
+impl Decode for char {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let value = u32::decode(input)?;
+        char::from_u32(value).ok_or(DecodeError::InvalidChar(value))
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_str().encode(out);
+    }
+}
+
+impl Decode for String {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = read_len(input)?;
+        let bytes = take(input, len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        for elem in self {
+            elem.encode(out);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = read_len(input)?;
+        let mut out = Vec::new();
+        for _ in 0..len {
+            out.push(T::decode(input)?);
+        }
+        Ok(out)
+    }
+}
+
+/// Nested lists are written like a `Vec`, starting at index -1.
+impl<T: Encode> Encode for List<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let elems = self.index_order();
+        elems.len().encode(out);
+        for elem in elems {
+            elem.encode(out);
+        }
+    }
+}
+
+impl<T: Decode> Decode for List<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = read_len(input)?;
+        let mut list = List::new();
+        for _ in 0..len {
+            list.push(T::decode(input)?);
+        }
+        Ok(list)
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Encode),+> Encode for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn encode(&self, out: &mut Vec<u8>) {
+                let ($($name,)+) = self;
+                $($name.encode(out);)+
+            }
+        }
+
+        impl<$($name: Decode),+> Decode for ($($name,)+) {
+            fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                Ok(($($name::decode(input)?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
@@ -0,0 +1,491 @@
+//! Lists whose elements expire, like DreamBerd's `const const name<2> = "Luke"!`.
+//!
+//! An element pushed to an [`ExpiringList`] lives for a number of operations on the list,
+//! for a duration, or forever. Expired elements are dropped lazily, the next time the list is used.
+//!
+//! Time comes from a [`Clock`]. [`SystemClock`] uses the real time,
+//! while [`ManualClock`] only moves when told to, which keeps tests deterministic.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use dreamberd_array::expiring::{ExpiringList, Lifetime, ManualClock};
+//!
+//! let clock = ManualClock::new();
+//! let mut list = ExpiringList::with_clock(clock.clone());
+//! list.push_with_lifetime("session", Lifetime::Duration(Duration::from_secs(20)));
+//! list.push_with_lifetime("flash", Lifetime::Ops(1));
+//!
+//! assert_eq!(list.len(), 2);
+//! assert_eq!(list.len(), 1);
+//!
+//! clock.advance(Duration::from_secs(20));
+//! assert!(list.is_empty());
+//! ```
+
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::List;
+
+/// How long an element stays in an [`ExpiringList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lifetime {
+    /// For this many operations after the one that added it, like DreamBerd's `<2>`.
+    Ops(u64),
+    /// For this long after it was added, like DreamBerd's `<20s>`.
+    Duration(Duration),
+    /// Until it is removed, like DreamBerd's `<Infinity>`.
+    Infinity,
+}
+
+/// A source of time for an [`ExpiringList`].
+pub trait Clock {
+    /// Returns the time passed since some fixed starting point.
+    fn now(&self) -> Duration;
+}
+
+/// The real time, measured from when the clock was created.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Creates a clock starting now.
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one and give another to the list.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use dreamberd_array::expiring::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let shared = clock.clone();
+/// clock.advance(Duration::from_secs(3));
+///
+/// assert_eq!(shared.now(), Duration::from_secs(3));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl ManualClock {
+    /// Creates a clock at zero.
+    pub fn new() -> Self {
+        ManualClock::default()
+    }
+
+    /// Moves the clock forward.
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+
+    /// Sets the clock to a given time.
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expiry {
+    /// Gone once the operation count reaches this.
+    Op(u64),
+    /// Gone once the clock reaches this.
+    At(Duration),
+    Never,
+}
+
+struct Entry<T> {
+    elem: T,
+    expiry: Expiry,
+}
+
+/// A `List` whose elements expire after a [`Lifetime`].
+///
+/// Every call to `push`, `pop`, `get`, `get_mut`, `insert`, `remove`, `iter`, `len` or `is_empty`
+/// counts as one operation, and first drops the elements that expired.
+pub struct ExpiringList<T, C: Clock = SystemClock> {
+    list: List<Entry<T>>,
+    clock: C,
+    ops: u64,
+    /// The earliest operation count at which an element expires.
+    next_op: Option<u64>,
+    /// The earliest time at which an element expires.
+    next_time: Option<Duration>,
+}
+
+impl<T> ExpiringList<T> {
+    /// Creates an empty list using the real time.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::ExpiringList;
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    ///
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// ```
+    pub fn new() -> Self {
+        ExpiringList::with_clock(SystemClock::new())
+    }
+}
+
+impl<T> Default for ExpiringList<T> {
+    fn default() -> Self {
+        ExpiringList::new()
+    }
+}
+
+impl<T, C: Clock> ExpiringList<T, C> {
+    /// Creates an empty list using the given clock.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use dreamberd_array::expiring::{ExpiringList, Lifetime, ManualClock};
+    ///
+    /// let clock = ManualClock::new();
+    /// let mut list = ExpiringList::with_clock(clock.clone());
+    /// list.push_with_lifetime(1, Lifetime::Duration(Duration::from_secs(1)));
+    ///
+    /// clock.advance(Duration::from_millis(999));
+    /// assert_eq!(list.len(), 1);
+    /// clock.advance(Duration::from_millis(1));
+    /// assert_eq!(list.len(), 0);
+    /// ```
+    pub fn with_clock(clock: C) -> Self {
+        ExpiringList {
+            list: List::new(),
+            clock,
+            ops: 0,
+            next_op: None,
+            next_time: None,
+        }
+    }
+
+    /// Returns the clock.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Pushes an element that never expires.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::ExpiringList;
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    pub fn push(&mut self, elem: T) {
+        self.push_with_lifetime(elem, Lifetime::Infinity);
+    }
+
+    /// Pushes an element that expires after `lifetime`.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::{ExpiringList, Lifetime};
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push_with_lifetime("Luke", Lifetime::Ops(2));
+    ///
+    /// assert_eq!(list.get(-1.0), Some(&"Luke"));
+    /// assert_eq!(list.get(-1.0), Some(&"Luke"));
+    /// assert_eq!(list.get(-1.0), None);
+    /// ```
+    pub fn push_with_lifetime(&mut self, elem: T, lifetime: Lifetime) {
+        self.tick();
+        let entry = self.entry(elem, lifetime);
+        self.list.push(entry);
+    }
+
+    /// Pops the first element, like [`List::pop`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::{ExpiringList, Lifetime};
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// list.push_with_lifetime(2, Lifetime::Ops(0));
+    ///
+    /// assert_eq!(list.pop(), Some(1));
+    /// assert_eq!(list.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.tick();
+        self.list.pop().map(|entry| entry.elem)
+    }
+
+    /// Returns the element at an index, like [`List::get`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::ExpiringList;
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.get(0.0), Some(&2));
+    /// assert_eq!(list.get(1.0), None);
+    /// ```
+    pub fn get(&mut self, index: f32) -> Option<&T> {
+        self.tick();
+        self.list.get(index).map(|entry| &entry.elem)
+    }
+
+    /// Returns a mutable reference to the element at an index, like [`List::get_mut`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::ExpiringList;
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// *list.get_mut(-1.0).unwrap() = 42;
+    ///
+    /// assert_eq!(list.get(-1.0), Some(&42));
+    /// ```
+    pub fn get_mut(&mut self, index: f32) -> Option<&mut T> {
+        self.tick();
+        self.list.get_mut(index).map(|entry| &mut entry.elem)
+    }
+
+    /// Inserts an element that never expires, like [`List::insert`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::ExpiringList;
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// list.push(2);
+    /// list.insert(-0.5, 42);
+    ///
+    /// assert_eq!(list.get(0.0), Some(&42));
+    /// ```
+    pub fn insert(&mut self, index: f32, elem: T) {
+        self.insert_with_lifetime(index, elem, Lifetime::Infinity);
+    }
+
+    /// Inserts an element that expires after `lifetime`.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::{ExpiringList, Lifetime};
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// list.push(2);
+    /// list.insert_with_lifetime(-0.5, 42, Lifetime::Ops(1));
+    ///
+    /// assert_eq!(list.get(0.0), Some(&42));
+    /// assert_eq!(list.get(0.0), Some(&2));
+    /// ```
+    pub fn insert_with_lifetime(&mut self, index: f32, elem: T, lifetime: Lifetime) {
+        self.tick();
+        let entry = self.entry(elem, lifetime);
+        self.list.insert(index, entry);
+    }
+
+    /// Removes the element at an index, like [`List::remove`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::ExpiringList;
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    ///
+    /// assert_eq!(list.remove(-1.0), Some(1));
+    /// assert_eq!(list.remove(-1.0), None);
+    /// ```
+    pub fn remove(&mut self, index: f32) -> Option<T> {
+        self.tick();
+        self.list.remove(index).map(|entry| entry.elem)
+    }
+
+    /// Returns the number of elements that have not expired.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::{ExpiringList, Lifetime};
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push_with_lifetime(1, Lifetime::Ops(1));
+    ///
+    /// assert_eq!(list.len(), 1);
+    /// assert_eq!(list.len(), 0);
+    /// ```
+    pub fn len(&mut self) -> usize {
+        self.tick();
+        self.list.len()
+    }
+
+    /// Returns `true` if every element has expired or been removed.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::ExpiringList;
+    ///
+    /// let mut list = ExpiringList::new();
+    /// assert!(list.is_empty());
+    ///
+    /// list.push(1);
+    /// assert!(!list.is_empty());
+    /// ```
+    pub fn is_empty(&mut self) -> bool {
+        self.tick();
+        self.list.is_empty()
+    }
+
+    /// Returns an iterator over the elements that have not expired, from the first one.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::expiring::{ExpiringList, Lifetime};
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// list.push_with_lifetime(2, Lifetime::Ops(1));
+    /// list.push(3);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &1]);
+    /// ```
+    pub fn iter(&mut self) -> Iter<'_, T> {
+        self.tick();
+        Iter(self.list.iter())
+    }
+
+    /// Returns the elements that have not expired, without their lifetimes.
+    ///
+    /// This does not count as an operation.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    /// use dreamberd_array::expiring::{ExpiringList, Lifetime};
+    ///
+    /// let mut list = ExpiringList::new();
+    /// list.push(1);
+    /// list.push_with_lifetime(2, Lifetime::Ops(0));
+    /// list.push(3);
+    ///
+    /// assert_eq!(list.into_list(), dlist![1, 3]);
+    /// ```
+    pub fn into_list(mut self) -> List<T> {
+        self.purge();
+        let entries: Vec<Entry<T>> = Vec::from(std::mem::take(&mut self.list));
+        entries.into_iter().map(|entry| entry.elem).collect::<Vec<T>>().into()
+    }
+
+    fn entry(&mut self, elem: T, lifetime: Lifetime) -> Entry<T> {
+        let expiry = match lifetime {
+            Lifetime::Ops(ops) => {
+                let op = self.ops.saturating_add(ops).saturating_add(1);
+                self.next_op = Some(self.next_op.map_or(op, |next| next.min(op)));
+                Expiry::Op(op)
+            }
+            Lifetime::Duration(duration) => {
+                let at = self.clock.now().saturating_add(duration);
+                self.next_time = Some(self.next_time.map_or(at, |next| next.min(at)));
+                Expiry::At(at)
+            }
+            Lifetime::Infinity => Expiry::Never,
+        };
+        Entry { elem, expiry }
+    }
+
+    /// Counts an operation and drops what expired.
+    fn tick(&mut self) {
+        self.ops += 1;
+        self.purge();
+    }
+
+    fn purge(&mut self) {
+        let now = self.clock.now();
+        let due = self.next_op.is_some_and(|op| op <= self.ops) || self.next_time.is_some_and(|at| at <= now);
+        if !due {
+            return;
+        }
+        let (ops, mut next_op, mut next_time) = (self.ops, None, None);
+        let entries: Vec<Entry<T>> = Vec::from(std::mem::take(&mut self.list));
+        let kept: Vec<Entry<T>> = entries
+            .into_iter()
+            .filter(|entry| match entry.expiry {
+                Expiry::Op(op) if op <= ops => false,
+                Expiry::At(at) if at <= now => false,
+                Expiry::Op(op) => {
+                    next_op = Some(next_op.map_or(op, |next: u64| next.min(op)));
+                    true
+                }
+                Expiry::At(at) => {
+                    next_time = Some(next_time.map_or(at, |next: Duration| next.min(at)));
+                    true
+                }
+                Expiry::Never => true,
+            })
+            .collect();
+        self.list = List::from(kept);
+        self.next_op = next_op;
+        self.next_time = next_time;
+    }
+}
+
+impl<T: fmt::Debug, C: Clock> fmt::Debug for ExpiringList<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<&Entry<T>> = self.list.iter().collect();
+        entries.reverse();
+        f.debug_list()
+            .entries(entries.into_iter().map(|entry| &entry.elem))
+            .finish()
+    }
+}
+
+/// An iterator over the elements of an [`ExpiringList`], returned by [`ExpiringList::iter`].
+pub struct Iter<'a, T>(crate::Iter<'a, Entry<T>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.0.next().map(|entry| &entry.elem)
+    }
+}
@@ -0,0 +1,460 @@
+//! A list with a fixed capacity that never allocates.
+//!
+//! [`FixedList`] keeps up to `N` elements inline, in an array, so it can live on the stack
+//! or in a `static` and be used where allocating is not an option.
+//! It resolves indexes exactly like [`List`]: index -1 is the first element pushed,
+//! and a fractional index rounds up to the next whole one.
+//!
+//! Adding to a full list returns a [`CapacityError`] holding the element instead of growing.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::fixed::{CapacityError, FixedList};
+//!
+//! let mut list: FixedList<i32, 3> = FixedList::new();
+//! list.push(1).unwrap();
+//! list.push(2).unwrap();
+//! list.insert(-0.5, 42).unwrap();
+//!
+//! assert_eq!(list[-1.0], 1);
+//! assert_eq!(list[0.0], 42);
+//! assert_eq!(list[1.0], 2);
+//! assert_eq!(list.push(3), Err(CapacityError(3)));
+//! ```
+
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::Rev;
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+use core::{ptr, slice};
+
+use crate::List;
+
+/// A list holding at most `N` elements inline, with the indexing of [`List`].
+pub struct FixedList<T, const N: usize> {
+    /// The elements starting at index -1; the first `len` are initialized.
+    elems: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+/// The error returned when adding to a full [`FixedList`], holding the element that was not added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the list is full")
+    }
+}
+
+impl<T: fmt::Debug> Error for CapacityError<T> {}
+
+/// Consumes a [`FixedList`], starting at the front like [`crate::IntoIter`].
+pub struct IntoIter<T, const N: usize>(FixedList<T, N>);
+
+/// An iterator over a [`FixedList`], starting at the front like [`crate::Iter`].
+pub struct Iter<'a, T>(Rev<slice::Iter<'a, T>>);
+
+/// A mutable iterator over a [`FixedList`], starting at the front like [`crate::IterMut`].
+pub struct IterMut<'a, T>(Rev<slice::IterMut<'a, T>>);
+
+impl<T, const N: usize> FixedList<T, N> {
+    /// Creates a new and empty `FixedList`.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::fixed::FixedList;
+    ///
+    /// let list: FixedList<i32, 4> = FixedList::new();
+    /// assert_eq!(list.len(), 0);
+    /// assert_eq!(list.capacity(), 4);
+    /// ```
+    pub const fn new() -> Self {
+        FixedList {
+            elems: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Returns how many elements the list can hold, which is always `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of elements in the list.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the list holds `N` elements.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Pushes an element to the front of the list, or returns it if the list is full.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::fixed::{CapacityError, FixedList};
+    ///
+    /// let mut list: FixedList<i32, 1> = FixedList::new();
+    ///
+    /// assert_eq!(list.push(1), Ok(()));
+    /// assert_eq!(list.push(2), Err(CapacityError(2)));
+    /// assert_eq!(list.peek(), Some(&1));
+    /// ```
+    pub fn push(&mut self, elem: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError(elem));
+        }
+        self.elems[self.len].write(elem);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element from the front of the list.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::fixed::FixedList;
+    ///
+    /// let mut list: FixedList<i32, 2> = FixedList::new();
+    /// list.push(1).unwrap();
+    /// list.push(2).unwrap();
+    ///
+    /// assert_eq!(list.pop(), Some(2));
+    /// assert_eq!(list.pop(), Some(1));
+    /// assert_eq!(list.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the slot was initialized and is no longer counted in `len`.
+        Some(unsafe { self.elems[self.len].assume_init_read() })
+    }
+
+    /// Returns a reference to the element at the front of the list.
+    pub fn peek(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    /// Returns a mutable reference to the element at the front of the list.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.as_mut_slice().last_mut()
+    }
+
+    /// Returns an iterator over the list, starting at the front.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::fixed::FixedList;
+    ///
+    /// let list: FixedList<i32, 3> = [1, 2, 3].into_iter().collect();
+    ///
+    /// let mut iter = list.iter();
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.as_slice().iter().rev())
+    }
+
+    /// Returns a mutable iterator over the list, starting at the front.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut(self.as_mut_slice().iter_mut().rev())
+    }
+
+    /// Returns the element at the given index.
+    ///
+    /// The index starts with -1 at the end of the list and is in fractional form.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::fixed::FixedList;
+    ///
+    /// let list: FixedList<i32, 3> = [1, 2, 3].into_iter().collect();
+    ///
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// assert_eq!(list.get(-0.5), Some(&2));
+    /// assert_eq!(list.get(1.0), Some(&3));
+    /// assert_eq!(list.get(2.0), None);
+    /// ```
+    pub fn get(&self, index: f32) -> Option<&T> {
+        self.position(index).map(|pos| &self.as_slice()[pos])
+    }
+
+    /// Returns a mutable reference to the element at the given index.
+    pub fn get_mut(&mut self, index: f32) -> Option<&mut T> {
+        self.position(index)
+            .map(|pos| &mut self.as_mut_slice()[pos])
+    }
+
+    /// Inserts an element at the given index, or returns it if the list is full.
+    ///
+    /// Like [`List::insert`], an index outside the list is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::fixed::{CapacityError, FixedList};
+    ///
+    /// let mut list: FixedList<i32, 3> = [1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(list.insert(0.0, 0), Ok(()));
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// assert_eq!(list.get(0.0), Some(&0));
+    /// assert_eq!(list.get(1.0), Some(&2));
+    /// assert_eq!(list.insert(0.0, 42), Err(CapacityError(42)));
+    /// ```
+    pub fn insert(&mut self, index: f32, elem: T) -> Result<(), CapacityError<T>> {
+        let Some(pos) = self.position(index) else {
+            return Ok(());
+        };
+        if self.is_full() {
+            return Err(CapacityError(elem));
+        }
+        self.insert_at(pos, elem);
+        Ok(())
+    }
+
+    /// Removes and returns the element at the given index.
+    ///
+    /// Fractional indexes remove the same element `get` would return.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::fixed::FixedList;
+    ///
+    /// let mut list: FixedList<i32, 3> = [1, 2, 3].into_iter().collect();
+    ///
+    /// assert_eq!(list.remove(0.0), Some(2));
+    /// assert_eq!(list.get(0.0), Some(&3));
+    /// assert_eq!(list.remove(1.0), None);
+    /// ```
+    pub fn remove(&mut self, index: f32) -> Option<T> {
+        let pos = self.position(index)?;
+        Some(self.remove_at(pos))
+    }
+
+    /// Removes every element.
+    pub fn clear(&mut self) {
+        let elems: *mut [T] = self.as_mut_slice();
+        // Forget the elements first, so a panicking `drop` cannot drop one twice.
+        self.len = 0;
+        // SAFETY: the elements were initialized and are no longer reachable through `self`.
+        unsafe { ptr::drop_in_place(elems) }
+    }
+
+    /// Inserts an element at a slot, moving the ones from there on up.
+    ///
+    /// # Panics
+    /// Panics if the list is full or `pos` is past the end.
+    pub(crate) fn insert_at(&mut self, pos: usize, elem: T) {
+        assert!(pos <= self.len && !self.is_full());
+        // SAFETY: there is room for one more, so shifting `pos..len` up by one stays in bounds.
+        unsafe {
+            let slot = self.elems.as_mut_ptr().cast::<T>().add(pos);
+            ptr::copy(slot, slot.add(1), self.len - pos);
+            slot.write(elem);
+        }
+        self.len += 1;
+    }
+
+    /// Removes the element at a slot, moving the ones after it down.
+    ///
+    /// # Panics
+    /// Panics if `pos` is not below the length.
+    pub(crate) fn remove_at(&mut self, pos: usize) -> T {
+        assert!(pos < self.len);
+        // SAFETY: `pos` is below `len`; the elements after it move down over the read-out slot.
+        let elem = unsafe {
+            let slot = self.elems.as_mut_ptr().cast::<T>().add(pos);
+            let elem = slot.read();
+            ptr::copy(slot.add(1), slot, self.len - pos - 1);
+            elem
+        };
+        self.len -= 1;
+        elem
+    }
+
+    /// Moves the first `count` elements onto the end of `other`, keeping their order.
+    ///
+    /// # Panics
+    /// Panics if there are fewer than `count` elements or `other` has no room for them.
+    pub(crate) fn move_front_to(&mut self, count: usize, other: &mut Self) {
+        assert!(count <= self.len && other.len + count <= N);
+        // SAFETY: both ranges are in bounds, and the moved slots are no longer counted in `self.len`.
+        unsafe {
+            let src = self.elems.as_mut_ptr().cast::<T>();
+            let dst = other.elems.as_mut_ptr().cast::<T>().add(other.len);
+            ptr::copy_nonoverlapping(src, dst, count);
+            ptr::copy(src.add(count), src, self.len - count);
+        }
+        self.len -= count;
+        other.len += count;
+    }
+
+    /// Returns the slot an index refers to, the same one `List::get` would pick.
+    fn position(&self, index: f32) -> Option<usize> {
+        crate::position(index, self.len)
+    }
+
+    /// The elements, starting at index -1.
+    pub(crate) fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` slots are initialized.
+        unsafe { slice::from_raw_parts(self.elems.as_ptr().cast(), self.len) }
+    }
+
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` slots are initialized.
+        unsafe { slice::from_raw_parts_mut(self.elems.as_mut_ptr().cast(), self.len) }
+    }
+}
+
+impl<T, const N: usize> Drop for FixedList<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for FixedList<T, N> {
+    fn default() -> Self {
+        FixedList::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for FixedList<T, N> {
+    fn clone(&self) -> Self {
+        let mut list = FixedList::new();
+        for elem in self.as_slice() {
+            list.elems[list.len].write(elem.clone());
+            list.len += 1;
+        }
+        list
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for FixedList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for FixedList<T, N> {}
+
+/// Hashes the elements like a slice, starting at index -1.
+impl<T: Hash, const N: usize> Hash for FixedList<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+/// Formats the elements like a slice, starting at index -1.
+impl<T: fmt::Debug, const N: usize> fmt::Debug for FixedList<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+/// Collects an iterator by pushing every element in turn, like `List`.
+///
+/// # Panics
+/// Panics if the iterator yields more than `N` elements.
+impl<T, const N: usize> FromIterator<T> for FixedList<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = FixedList::new();
+        for elem in iter {
+            if list.push(elem).is_err() {
+                panic!("collected more than {} elements into a FixedList", N);
+            }
+        }
+        list
+    }
+}
+
+/// Moves the elements into a `List`, keeping their indexes.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::{dlist, fixed::FixedList};
+///
+/// let list: FixedList<i32, 4> = [1, 2, 3].into_iter().collect();
+///
+/// assert_eq!(dreamberd_array::List::from(list), dlist![1, 2, 3]);
+/// ```
+impl<T, const N: usize> From<FixedList<T, N>> for List<T> {
+    fn from(list: FixedList<T, N>) -> Self {
+        let mut elems: Vec<T> = list.into_iter().collect();
+        elems.reverse();
+        List::from(elems)
+    }
+}
+
+impl<T, const N: usize> IntoIterator for FixedList<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a FixedList<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut FixedList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<T, const N: usize> Index<f32> for FixedList<T, N> {
+    type Output = T;
+    fn index(&self, index: f32) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T, const N: usize> IndexMut<f32> for FixedList<T, N> {
+    fn index_mut(&mut self, index: f32) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
@@ -0,0 +1,416 @@
+//! Lists that remember what each slot held before, like DreamBerd's `previous` keyword.
+//!
+//! A [`HistoryList`] remembers the old value of a slot whenever it is overwritten through
+//! `get_mut` or `IndexMut`, or changes because `insert` or `remove` shifted the elements after it.
+//! History belongs to the index, not to the element: after inserting at `0`,
+//! the history of index `0` starts with the element that used to be there.
+//!
+//! Only the changes themselves are recorded: the old value of an overwrite, the index of an insert
+//! and the removed element of a remove. The shifts are replayed when a history is read,
+//! so an edit costs the same no matter how many elements it moves.
+//! At most [`HistoryList::retention`] changes are kept, dropping the oldest first.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::history::HistoryList;
+//!
+//! let mut list = HistoryList::new();
+//! list.push("draft");
+//! list[-1.0] = "review";
+//! list[-1.0] = "final";
+//!
+//! assert_eq!(list.previous(-1.0, 0), Some(&"final"));
+//! assert_eq!(list.previous(-1.0, 1), Some(&"review"));
+//! assert_eq!(list.history(-1.0).unwrap().collect::<Vec<_>>(), vec![&"review", &"draft"]);
+//! ```
+
+use alloc::collections::VecDeque;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+use crate::{Iter, List};
+
+/// A `List` that keeps the previous values of every index.
+pub struct HistoryList<T> {
+    list: List<T>,
+    /// Every change, oldest first.
+    changes: VecDeque<Change<T>>,
+    retention: Option<usize>,
+}
+
+/// One change to the list, with positions counted from index -1.
+enum Change<T> {
+    /// The element at `position` was overwritten, and used to be `old`.
+    Set { position: usize, old: T },
+    /// An element was inserted at `position`, shifting the ones from there up.
+    Insert { position: usize },
+    /// `elem` was removed from `position`, shifting the ones above it down.
+    Remove { position: usize, elem: T },
+}
+
+impl<T> HistoryList<T> {
+    /// Creates an empty list that keeps every change.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let list: HistoryList<i32> = HistoryList::new();
+    ///
+    /// assert_eq!(list.retention(), None);
+    /// ```
+    pub fn new() -> Self {
+        HistoryList {
+            list: List::new(),
+            changes: VecDeque::new(),
+            retention: None,
+        }
+    }
+
+    /// Creates an empty list that keeps at most `retention` changes.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::with_retention(1);
+    /// list.push(1);
+    /// list[-1.0] = 2;
+    /// list[-1.0] = 3;
+    ///
+    /// assert_eq!(list.previous(-1.0, 1), Some(&2));
+    /// assert_eq!(list.previous(-1.0, 2), None);
+    /// ```
+    pub fn with_retention(retention: usize) -> Self {
+        HistoryList {
+            retention: Some(retention),
+            ..HistoryList::new()
+        }
+    }
+
+    /// Returns how many changes are kept, or `None` if there is no limit.
+    pub fn retention(&self) -> Option<usize> {
+        self.retention
+    }
+
+    /// Changes how many changes are kept, dropping the oldest ones above the new limit.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push(1);
+    /// list[-1.0] = 2;
+    /// list[-1.0] = 3;
+    /// list.set_retention(Some(1));
+    ///
+    /// assert_eq!(list.history(-1.0).unwrap().count(), 1);
+    /// ```
+    pub fn set_retention(&mut self, retention: Option<usize>) {
+        self.retention = retention;
+        self.trim();
+    }
+
+    /// Pushes an element to a new index, like [`List::push`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert_eq!(list.get(0.0), Some(&2));
+    /// assert_eq!(list.history(0.0).unwrap().count(), 0);
+    /// ```
+    pub fn push(&mut self, elem: T) {
+        let position = self.list.len();
+        self.list.push(elem);
+        self.record(Change::Insert { position });
+    }
+
+    /// Returns the element at an index, like [`List::get`].
+    pub fn get(&self, index: f32) -> Option<&T> {
+        self.list.get(index)
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns an iterator over the current values, like [`List::iter`].
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Returns the current values.
+    pub fn as_list(&self) -> &List<T> {
+        &self.list
+    }
+
+    /// Returns the current values, dropping the history.
+    pub fn into_list(self) -> List<T> {
+        self.list
+    }
+
+    /// Returns the value an index held `n` changes ago, where `0` is the current value.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push(1);
+    /// list.push(2);
+    /// list.insert(-0.5, 42);
+    ///
+    /// assert_eq!(list.previous(0.0, 0), Some(&42));
+    /// assert_eq!(list.previous(0.0, 1), Some(&2));
+    /// assert_eq!(list.previous(0.0, 2), None);
+    /// ```
+    pub fn previous(&self, index: f32, n: usize) -> Option<&T> {
+        match n {
+            0 => self.list.get(index),
+            n => self.history(index)?.nth(n - 1),
+        }
+    }
+
+    /// Returns the old values of an index, newest first, or `None` if the index is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push(1);
+    /// *list.get_mut(-1.0).unwrap() = 2;
+    ///
+    /// assert_eq!(list.history(-1.0).unwrap().collect::<Vec<_>>(), vec![&1]);
+    /// assert!(list.history(0.0).is_none());
+    /// ```
+    pub fn history(&self, index: f32) -> Option<History<'_, T>> {
+        Some(History {
+            list: self,
+            position: crate::position(index, self.list.len())?,
+            changes: self.changes.len(),
+            len: self.list.len(),
+        })
+    }
+
+    /// Forgets every old value, keeping the current ones.
+    pub fn clear_history(&mut self) {
+        self.changes.clear();
+    }
+
+    fn record(&mut self, change: Change<T>) {
+        self.changes.push_back(change);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        if let Some(retention) = self.retention {
+            let excess = self.changes.len().saturating_sub(retention);
+            self.changes.drain(..excess);
+        }
+    }
+
+    /// Returns the element at `position` right after the first `changes` changes,
+    /// by following it through the later ones.
+    fn value_after(&self, changes: usize, mut position: usize) -> Option<&T> {
+        for change in self.changes.range(changes..) {
+            match *change {
+                Change::Set { position: at, ref old } if at == position => return Some(old),
+                Change::Insert { position: at } if at <= position => position += 1,
+                Change::Remove { position: at, ref elem } if at == position => return Some(elem),
+                Change::Remove { position: at, .. } if at < position => position -= 1,
+                _ => {}
+            }
+        }
+        self.list.get(position as f32 - 1.0)
+    }
+}
+
+/// The old values of one index, newest first, returned by [`HistoryList::history`].
+pub struct History<'a, T> {
+    list: &'a HistoryList<T>,
+    position: usize,
+    /// How many changes are left to walk back through.
+    changes: usize,
+    /// The length of the list after those changes.
+    len: usize,
+}
+
+impl<'a, T> Iterator for History<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let list = self.list;
+        while self.changes > 0 {
+            self.changes -= 1;
+            let after = self.changes + 1;
+            match list.changes[self.changes] {
+                Change::Set { position, ref old } if position == self.position => return Some(old),
+                Change::Set { .. } => {}
+                Change::Insert { position } => {
+                    self.len -= 1;
+                    if self.position >= self.len {
+                        // The index did not exist before this insert.
+                        self.changes = 0;
+                    } else if position <= self.position {
+                        return list.value_after(after, self.position + 1);
+                    }
+                }
+                Change::Remove { position, ref elem } => {
+                    self.len += 1;
+                    if position == self.position {
+                        return Some(elem);
+                    } else if position < self.position {
+                        return list.value_after(after, self.position - 1);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<T: Clone> HistoryList<T> {
+    /// Pops the element with the highest index, like [`List::pop`], forgetting that index's history.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push(1);
+    /// list[-1.0] = 2;
+    ///
+    /// assert_eq!(list.pop(), Some(2));
+    /// assert!(list.history(-1.0).is_none());
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        let elem = self.list.pop()?;
+        let position = self.list.len();
+        self.record(Change::Remove {
+            position,
+            elem: elem.clone(),
+        });
+        Some(elem)
+    }
+
+    /// Returns a mutable reference to the element at an index, like [`List::get_mut`].
+    ///
+    /// The current value is recorded first, even if it ends up unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push(1);
+    /// *list.get_mut(-1.0).unwrap() += 1;
+    ///
+    /// assert_eq!(list.previous(-1.0, 1), Some(&1));
+    /// assert_eq!(list.get_mut(0.0), None);
+    /// ```
+    pub fn get_mut(&mut self, index: f32) -> Option<&mut T> {
+        let position = crate::position(index, self.list.len())?;
+        let old = self.list.get(index)?.clone();
+        self.record(Change::Set { position, old });
+        self.list.get_mut(index)
+    }
+
+    /// Inserts an element at an index, like [`List::insert`].
+    ///
+    /// Every index from the inserted one up records the value that was shifted away from it.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push('a');
+    /// list.push('b');
+    /// list.insert(-0.5, 'x');
+    ///
+    /// assert_eq!(list.previous(0.0, 1), Some(&'b'));
+    /// assert_eq!(list.previous(1.0, 1), None);
+    /// ```
+    pub fn insert(&mut self, index: f32, elem: T) {
+        let Some(position) = crate::position(index, self.list.len()) else {
+            return;
+        };
+        self.list.insert(index, elem);
+        self.record(Change::Insert { position });
+    }
+
+    /// Removes the element at an index, like [`List::remove`].
+    ///
+    /// Every index from the removed one up records the value it held before the elements shifted down,
+    /// and the history of the highest index is forgotten.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::history::HistoryList;
+    ///
+    /// let mut list = HistoryList::new();
+    /// list.push('a');
+    /// list.push('b');
+    ///
+    /// assert_eq!(list.remove(-1.0), Some('a'));
+    /// assert_eq!(list.previous(-1.0, 1), Some(&'a'));
+    /// ```
+    pub fn remove(&mut self, index: f32) -> Option<T> {
+        let position = crate::position(index, self.list.len())?;
+        let elem = self.list.remove(index)?;
+        self.record(Change::Remove {
+            position,
+            elem: elem.clone(),
+        });
+        Some(elem)
+    }
+}
+
+impl<T> Default for HistoryList<T> {
+    fn default() -> Self {
+        HistoryList::new()
+    }
+}
+
+impl<T> From<List<T>> for HistoryList<T> {
+    fn from(list: List<T>) -> Self {
+        HistoryList {
+            list,
+            changes: VecDeque::new(),
+            retention: None,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for HistoryList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.list, f)
+    }
+}
+
+impl<T> Index<f32> for HistoryList<T> {
+    type Output = T;
+
+    fn index(&self, index: f32) -> &T {
+        &self.list[index]
+    }
+}
+
+impl<T: Clone> IndexMut<f32> for HistoryList<T> {
+    fn index_mut(&mut self, index: f32) -> &mut T {
+        self.get_mut(index).unwrap()
+    }
+}
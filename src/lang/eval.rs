@@ -0,0 +1,414 @@
+//! Runs statements against `List<Value>`s.
+
+use std::collections::HashMap;
+
+use super::lexer::tokenize;
+use super::parser::{parse, BinaryOp, Expr, ExprKind, Stmt, StmtKind};
+use super::{LangError, LangErrorKind};
+use crate::syntax::Keyword;
+use crate::value::Bool;
+use crate::{List, Value};
+
+const UNDEFINED: Value = Value::Undefined;
+
+struct Variable {
+    binding: Keyword,
+    contents: Keyword,
+    value: Value,
+}
+
+/// Runs DreamBerd programs, keeping variables between runs.
+///
+/// Everything printed, by `print` or by statements ending in `?`, is collected in [`Interpreter::output`].
+///
+/// # Example
+/// ```
+/// use dreamberd_array::lang::Interpreter;
+/// use dreamberd_array::Value;
+///
+/// let mut interpreter = Interpreter::new();
+/// interpreter.run("const var xs = [1, 2, 3]!").unwrap();
+/// interpreter.run("xs[0.5] = 42!").unwrap();
+/// interpreter.run("print(xs)!").unwrap();
+///
+/// assert_eq!(interpreter.output(), "[1, 2, 42, 3]\n");
+/// assert_eq!(interpreter.get("xs"), Some(&Value::from(vec![1, 2, 42, 3])));
+/// ```
+#[derive(Default)]
+pub struct Interpreter {
+    vars: HashMap<String, Variable>,
+    output: String,
+}
+
+impl Interpreter {
+    /// Creates an interpreter without any variables.
+    pub fn new() -> Self {
+        Interpreter {
+            vars: HashMap::new(),
+            output: String::new(),
+        }
+    }
+
+    /// Runs a program.
+    ///
+    /// Nothing runs if the program does not parse.
+    /// A runtime error stops the program, keeping the effects of the statements before it.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::lang::{Interpreter, LangErrorKind};
+    ///
+    /// let mut interpreter = Interpreter::new();
+    /// let err = interpreter.run("const const xs = [1]!\nxs[-1] = 2!").unwrap_err();
+    ///
+    /// assert_eq!(err.kind, LangErrorKind::Mutate("xs".to_string()));
+    /// assert_eq!((err.line, err.column), (2, 1));
+    /// ```
+    pub fn run(&mut self, src: &str) -> Result<(), LangError> {
+        let stmts = parse(tokenize(src)?)?;
+        for stmt in &stmts {
+            self.stmt(stmt)?;
+        }
+        Ok(())
+    }
+
+    /// Returns everything printed so far.
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    /// Returns everything printed so far and clears it.
+    pub fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Returns the current value of a variable.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.vars.get(name).map(|var| &var.value)
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> Result<(), LangError> {
+        let value = match &stmt.kind {
+            StmtKind::Declare {
+                binding,
+                contents,
+                name,
+                value,
+            } => {
+                let value = self.eval(value)?;
+                self.vars.insert(
+                    name.clone(),
+                    Variable {
+                        binding: *binding,
+                        contents: *contents,
+                        value: value.clone(),
+                    },
+                );
+                value
+            }
+            StmtKind::Assign { name, path, value } => {
+                let value = self.eval(value)?;
+                self.assign(stmt, name, path, value.clone())?;
+                value
+            }
+            StmtKind::Expr(expr) => self.eval(expr)?,
+        };
+        if stmt.debug {
+            self.output.push_str(&format!("{}\n", value));
+        }
+        Ok(())
+    }
+
+    fn assign(&mut self, stmt: &Stmt, name: &str, path: &[Expr], value: Value) -> Result<(), LangError> {
+        let Some((last, parents)) = path.split_last() else {
+            let var = self.var_mut(name, stmt)?;
+            if var.binding == Keyword::Const {
+                return Err(error(LangErrorKind::Reassign(name.to_string()), stmt.line, stmt.column));
+            }
+            var.value = value;
+            return Ok(());
+        };
+        let index = self.index(last)?;
+        let list = self.list_at(name, parents, stmt.line, stmt.column)?;
+        // Whole indexes overwrite, fractional ones insert in between.
+        if index.fract() == 0.0 {
+            match list.get_mut(index) {
+                Some(slot) => *slot = value,
+                None => return Err(error(LangErrorKind::OutOfBounds(index), last.line, last.column)),
+            }
+        } else {
+            if list.get(index).is_none() {
+                return Err(error(LangErrorKind::OutOfBounds(index), last.line, last.column));
+            }
+            list.insert(index, value);
+        }
+        Ok(())
+    }
+
+    fn var_mut(&mut self, name: &str, at: &Stmt) -> Result<&mut Variable, LangError> {
+        self.vars
+            .get_mut(name)
+            .ok_or_else(|| error(LangErrorKind::UndefinedVariable(name.to_string()), at.line, at.column))
+    }
+
+    /// Returns the list found by following `path` from the variable `name`, checking it may be changed.
+    fn list_at(&mut self, name: &str, path: &[Expr], line: usize, column: usize) -> Result<&mut List<Value>, LangError> {
+        let mut indexes = Vec::new();
+        for expr in path {
+            indexes.push((self.index(expr)?, expr));
+        }
+        let var = self
+            .vars
+            .get_mut(name)
+            .ok_or_else(|| error(LangErrorKind::UndefinedVariable(name.to_string()), line, column))?;
+        if var.contents == Keyword::Const {
+            return Err(error(LangErrorKind::Mutate(name.to_string()), line, column));
+        }
+        let mut current = &mut var.value;
+        for (index, expr) in indexes {
+            current = match current {
+                Value::List(list) => list
+                    .get_mut(index)
+                    .ok_or_else(|| error(LangErrorKind::OutOfBounds(index), expr.line, expr.column))?,
+                other => return Err(type_error(format!("cannot index into {}", type_name(other)), expr)),
+            };
+        }
+        match current {
+            Value::List(list) => Ok(list),
+            other => Err(error(
+                LangErrorKind::Type(format!("expected a list, found {}", type_name(other))),
+                line,
+                column,
+            )),
+        }
+    }
+
+    /// Returns the list a mutating builtin like `push` works on.
+    fn target(&mut self, expr: &Expr) -> Result<&mut List<Value>, LangError> {
+        let mut path = Vec::new();
+        let mut current = expr;
+        loop {
+            match &current.kind {
+                ExprKind::Var(name) => {
+                    path.reverse();
+                    let path: Vec<Expr> = path.into_iter().cloned().collect();
+                    return self.list_at(name, &path, expr.line, expr.column);
+                }
+                ExprKind::Index(inner, index) => {
+                    path.push(&**index);
+                    current = inner;
+                }
+                _ => return Err(error(LangErrorKind::InvalidAssignment, expr.line, expr.column)),
+            }
+        }
+    }
+
+    fn index(&mut self, expr: &Expr) -> Result<f32, LangError> {
+        match self.eval(expr)? {
+            Value::Number(number) if number.is_finite() => Ok(number as f32),
+            other => Err(type_error(format!("cannot index with {}", type_name(&other)), expr)),
+        }
+    }
+
+    fn eval(&mut self, expr: &Expr) -> Result<Value, LangError> {
+        match &expr.kind {
+            ExprKind::Literal(value) => Ok(value.clone()),
+            ExprKind::Var(name) => self
+                .get(name)
+                .cloned()
+                .ok_or_else(|| error(LangErrorKind::UndefinedVariable(name.clone()), expr.line, expr.column)),
+            ExprKind::List(items) => {
+                let mut elems = Vec::new();
+                for item in items {
+                    elems.push(self.eval(item)?);
+                }
+                Ok(Value::List(List::from(elems)))
+            }
+            ExprKind::Index(..) => self.element(expr),
+            ExprKind::Call(name, args) => self.call(expr, name, args),
+            ExprKind::Neg(operand) => match self.eval(operand)? {
+                Value::Number(number) => Ok(Value::Number(-number)),
+                other => Err(type_error(format!("cannot negate {}", type_name(&other)), expr)),
+            },
+            ExprKind::Binary(op, left, right) => {
+                let left = self.eval(left)?;
+                let right = self.eval(right)?;
+                binary(*op, left, right).map_err(|message| type_error(message, expr))
+            }
+        }
+    }
+
+    /// Reads an element such as `xs[i][j]`, looking variables up in place so only the element is cloned.
+    fn element(&mut self, expr: &Expr) -> Result<Value, LangError> {
+        let mut path = Vec::new();
+        let mut base = expr;
+        while let ExprKind::Index(inner, index) = &base.kind {
+            path.push((&**index, base));
+            base = inner;
+        }
+        path.reverse();
+        let owned = match &base.kind {
+            ExprKind::Var(name) => Err(name),
+            _ => Ok(self.eval(base)?),
+        };
+        let mut indexes = Vec::with_capacity(path.len());
+        for (index, at) in path {
+            indexes.push((self.index(index)?, at));
+        }
+        let mut current = match &owned {
+            Ok(value) => value,
+            Err(name) => self
+                .get(name)
+                .ok_or_else(|| error(LangErrorKind::UndefinedVariable(name.to_string()), base.line, base.column))?,
+        };
+        for (index, at) in indexes {
+            current = match current {
+                // Reading outside of the list is `undefined`, like in JavaScript.
+                Value::List(list) => list.get(index).unwrap_or(&UNDEFINED),
+                other => return Err(type_error(format!("cannot index into {}", type_name(other)), at)),
+            };
+        }
+        Ok(current.clone())
+    }
+
+    fn call(&mut self, expr: &Expr, name: &str, args: &[Expr]) -> Result<Value, LangError> {
+        let expected = match name {
+            "print" => {
+                let mut parts = Vec::new();
+                for arg in args {
+                    parts.push(match self.eval(arg)? {
+                        Value::String(string) => string,
+                        other => other.to_string(),
+                    });
+                }
+                self.output.push_str(&parts.join(" "));
+                self.output.push('\n');
+                return Ok(Value::Undefined);
+            }
+            "len" | "pop" => 1,
+            "push" | "remove" => 2,
+            "insert" => 3,
+            other => {
+                return Err(error(
+                    LangErrorKind::UnknownFunction(other.to_string()),
+                    expr.line,
+                    expr.column,
+                ))
+            }
+        };
+        if args.len() != expected {
+            return Err(error(
+                LangErrorKind::Arity {
+                    name: name.to_string(),
+                    expected,
+                    found: args.len(),
+                },
+                expr.line,
+                expr.column,
+            ));
+        }
+        match name {
+            "len" => match self.eval(&args[0])? {
+                Value::List(list) => Ok(Value::from(list.len())),
+                Value::String(string) => Ok(Value::from(string.chars().count())),
+                other => Err(type_error(format!("{} has no length", type_name(&other)), &args[0])),
+            },
+            "pop" => Ok(self.target(&args[0])?.pop().unwrap_or_default()),
+            "push" => {
+                let value = self.eval(&args[1])?;
+                self.target(&args[0])?.push(value);
+                Ok(Value::Undefined)
+            }
+            "remove" => {
+                let index = self.index(&args[1])?;
+                self.target(&args[0])?
+                    .remove(index)
+                    .ok_or_else(|| error(LangErrorKind::OutOfBounds(index), args[1].line, args[1].column))
+            }
+            _ => {
+                let index = self.index(&args[1])?;
+                let value = self.eval(&args[2])?;
+                let list = self.target(&args[0])?;
+                if list.get(index).is_none() {
+                    return Err(error(LangErrorKind::OutOfBounds(index), args[1].line, args[1].column));
+                }
+                list.insert(index, value);
+                Ok(Value::Undefined)
+            }
+        }
+    }
+}
+
+fn binary(op: BinaryOp, left: Value, right: Value) -> Result<Value, String> {
+    use Value::{Number, String as Str};
+    match (op, left, right) {
+        (BinaryOp::Eq, left, right) => Ok(Value::Bool((left == right).into())),
+        (BinaryOp::Add, Str(left), right) => Ok(Str(left + &plain(right))),
+        (BinaryOp::Add, left, Str(right)) => Ok(Str(plain(left) + &right)),
+        (op, Number(left), Number(right)) => Ok(match op {
+            BinaryOp::Add => Number(left + right),
+            BinaryOp::Sub => Number(left - right),
+            BinaryOp::Mul => Number(left * right),
+            BinaryOp::Div => Number(left / right),
+            BinaryOp::Rem => Number(left % right),
+            BinaryOp::Less => Value::Bool((left < right).into()),
+            BinaryOp::Greater => Value::Bool((left > right).into()),
+            BinaryOp::LessEq => Value::Bool((left <= right).into()),
+            BinaryOp::GreaterEq => Value::Bool((left >= right).into()),
+            BinaryOp::Eq => unreachable!(),
+        }),
+        (BinaryOp::Less, Str(left), Str(right)) => Ok(Value::Bool((left < right).into())),
+        (BinaryOp::Greater, Str(left), Str(right)) => Ok(Value::Bool((left > right).into())),
+        (BinaryOp::LessEq, Str(left), Str(right)) => Ok(Value::Bool((left <= right).into())),
+        (BinaryOp::GreaterEq, Str(left), Str(right)) => Ok(Value::Bool((left >= right).into())),
+        (op, left, right) => Err(format!(
+            "cannot apply {} to {} and {}",
+            op_name(op),
+            type_name(&left),
+            type_name(&right)
+        )),
+    }
+}
+
+/// Formats a value for `print` and string concatenation, without quoting strings.
+fn plain(value: Value) -> String {
+    match value {
+        Value::String(string) => string,
+        other => other.to_string(),
+    }
+}
+
+fn op_name(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "`+`",
+        BinaryOp::Sub => "`-`",
+        BinaryOp::Mul => "`*`",
+        BinaryOp::Div => "`/`",
+        BinaryOp::Rem => "`%`",
+        BinaryOp::Less => "`<`",
+        BinaryOp::Greater => "`>`",
+        BinaryOp::LessEq => "`<=`",
+        BinaryOp::GreaterEq => "`>=`",
+        BinaryOp::Eq => "`==`",
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Bool(Bool::Maybe) => "maybe",
+        Value::Bool(_) => "a boolean",
+        Value::Undefined => "undefined",
+        Value::List(_) => "a list",
+        Value::Map(_) => "a map",
+    }
+}
+
+fn error(kind: LangErrorKind, line: usize, column: usize) -> LangError {
+    LangError { kind, line, column }
+}
+
+fn type_error(message: String, at: &Expr) -> LangError {
+    error(LangErrorKind::Type(message), at.line, at.column)
+}
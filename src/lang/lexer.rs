@@ -0,0 +1,186 @@
+//! Turns source text into tokens.
+
+use super::{LangError, LangErrorKind};
+use crate::syntax::Scanner;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    Comma,
+    Assign,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Less,
+    Greater,
+    LessEq,
+    GreaterEq,
+    EqEq,
+    /// One or more `!`, ending a statement.
+    Bang(usize),
+    /// A `?`, ending a statement and printing its value.
+    Question,
+    Eof,
+}
+
+impl Token {
+    /// How the token is shown in error messages.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Token::Number(number) => format!("number {}", number),
+            Token::Str(string) => format!("string {:?}", string),
+            Token::Ident(name) => format!("`{}`", name),
+            Token::LBracket => "`[`".to_string(),
+            Token::RBracket => "`]`".to_string(),
+            Token::LParen => "`(`".to_string(),
+            Token::RParen => "`)`".to_string(),
+            Token::Comma => "`,`".to_string(),
+            Token::Assign => "`=`".to_string(),
+            Token::Plus => "`+`".to_string(),
+            Token::Minus => "`-`".to_string(),
+            Token::Star => "`*`".to_string(),
+            Token::Slash => "`/`".to_string(),
+            Token::Percent => "`%`".to_string(),
+            Token::Less => "`<`".to_string(),
+            Token::Greater => "`>`".to_string(),
+            Token::LessEq => "`<=`".to_string(),
+            Token::GreaterEq => "`>=`".to_string(),
+            Token::EqEq => "`==`".to_string(),
+            Token::Bang(count) => format!("`{}`", "!".repeat(*count)),
+            Token::Question => "`?`".to_string(),
+            Token::Eof => "end of input".to_string(),
+        }
+    }
+}
+
+/// A token and the 1-based line and column it starts at.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Spanned {
+    pub token: Token,
+    pub line: usize,
+    pub column: usize,
+}
+
+pub(crate) fn tokenize(src: &str) -> Result<Vec<Spanned>, LangError> {
+    let mut lexer = Lexer {
+        scanner: Scanner::new(src),
+    };
+    let mut tokens = Vec::new();
+    loop {
+        let spanned = lexer.next_token()?;
+        let done = spanned.token == Token::Eof;
+        tokens.push(spanned);
+        if done {
+            return Ok(tokens);
+        }
+    }
+}
+
+struct Lexer<'a> {
+    scanner: Scanner<'a>,
+}
+
+impl<'a> Lexer<'a> {
+    fn peek(&mut self) -> Option<char> {
+        self.scanner.peek()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        self.scanner.bump()
+    }
+
+    /// Skips whitespace and `//` comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') => {
+                    if self.scanner.peek_second() != Some('/') {
+                        return;
+                    }
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.bump();
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Spanned, LangError> {
+        self.skip_trivia();
+        let (line, column) = (self.scanner.line, self.scanner.column);
+        let token = match self.peek() {
+            None => Token::Eof,
+            Some(c) if c.is_ascii_digit() || c == '.' => Token::Number(self.scanner.number()?),
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+                    name.push(c);
+                    self.bump();
+                }
+                Token::Ident(name)
+            }
+            Some('"' | '\'') => Token::Str(self.scanner.string()?),
+            Some(c) => {
+                self.bump();
+                match c {
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    ',' => Token::Comma,
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    '/' => Token::Slash,
+                    '%' => Token::Percent,
+                    '?' => Token::Question,
+                    '!' => {
+                        let mut count = 1;
+                        while self.peek() == Some('!') {
+                            self.bump();
+                            count += 1;
+                        }
+                        Token::Bang(count)
+                    }
+                    '<' => self.with_eq(Token::Less, Token::LessEq),
+                    '>' => self.with_eq(Token::Greater, Token::GreaterEq),
+                    '=' => self.with_eq(Token::Assign, Token::EqEq),
+                    c => {
+                        return Err(LangError {
+                            kind: LangErrorKind::UnexpectedChar(c),
+                            line,
+                            column,
+                        })
+                    }
+                }
+            }
+        };
+        Ok(Spanned {
+            token,
+            line,
+            column,
+        })
+    }
+
+    /// Returns `with` if the next character is `=`, consuming it, and `without` otherwise.
+    fn with_eq(&mut self, without: Token, with: Token) -> Token {
+        if self.peek() == Some('=') {
+            self.bump();
+            with
+        } else {
+            without
+        }
+    }
+}
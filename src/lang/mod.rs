@@ -0,0 +1,153 @@
+//! A small interpreter for the array parts of DreamBerd, built on `List<Value>`.
+//!
+//! Supported are
+//!
+//! - declarations with all four mutability levels: `const const`, `const var`, `var const` and `var var`,
+//! - reassignment `xs = [1]!` and element assignment `xs[1] = 2!`,
+//!   where a fractional index like `xs[0.5] = 2!` inserts in between,
+//! - numbers, strings, `true`, `false`, `maybe`, `undefined` and nested array literals,
+//! - indexing with arbitrary expressions, such as `xs[i + 0.5]`,
+//! - `+`, `-`, `*`, `/`, `%`, `<`, `>`, `<=`, `>=` and `==`,
+//! - the functions `print`, `len`, `push`, `pop`, `insert` and `remove`,
+//! - `//` comments.
+//!
+//! Every statement ends in one or more `!`. Ending it in `?` instead also prints its value.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::lang;
+//!
+//! let output = lang::run("
+//!     const var xs = [1, 2, 3]!
+//!     const const i = 0!
+//!     xs[i + 0.5] = 42!
+//!     print(xs[-1], xs[1])!
+//!     xs?
+//! ").unwrap();
+//!
+//! assert_eq!(output, "1 42\n[1, 2, 42, 3]\n");
+//! ```
+
+mod eval;
+mod lexer;
+mod parser;
+
+use std::error::Error;
+use std::fmt;
+
+use crate::syntax::{ParseError, ParseErrorKind};
+
+pub use eval::Interpreter;
+
+/// What went wrong while running a program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LangErrorKind {
+    /// A character that does not start any token.
+    UnexpectedChar(char),
+    /// A string without its closing quote.
+    UnterminatedString,
+    /// An escape sequence that is not known.
+    InvalidEscape(char),
+    /// Something that looked like a number but is not one.
+    InvalidNumber(String),
+    /// A token that is not allowed at this point.
+    UnexpectedToken(String),
+    /// The program ended in the middle of a statement.
+    UnexpectedEnd,
+    /// An expression nested deeper than the parser allows.
+    NestingTooDeep,
+    /// The left side of `=` is not a variable or an indexed variable.
+    InvalidAssignment,
+    /// A variable that was never declared.
+    UndefinedVariable(String),
+    /// Reassigning a `const` binding.
+    Reassign(String),
+    /// Changing the contents of a `const` list.
+    Mutate(String),
+    /// An operation on values of the wrong type.
+    Type(String),
+    /// An index that does not point into the list.
+    OutOfBounds(f32),
+    /// A call to a function that does not exist.
+    UnknownFunction(String),
+    /// A call with the wrong number of arguments.
+    Arity {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// An error, with the 1-based line and column where it was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LangError {
+    pub kind: LangErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for LangError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            LangErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            LangErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            LangErrorKind::InvalidEscape(c) => write!(f, "invalid escape \\{}", c),
+            LangErrorKind::InvalidNumber(number) => write!(f, "invalid number {:?}", number),
+            LangErrorKind::UnexpectedToken(token) => write!(f, "unexpected {}", token),
+            LangErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            LangErrorKind::NestingTooDeep => write!(f, "expression nested too deeply"),
+            LangErrorKind::InvalidAssignment => write!(f, "can only assign to variables and their elements"),
+            LangErrorKind::UndefinedVariable(name) => write!(f, "undefined variable `{}`", name),
+            LangErrorKind::Reassign(name) => write!(f, "cannot reassign const `{}`", name),
+            LangErrorKind::Mutate(name) => write!(f, "cannot change the contents of const `{}`", name),
+            LangErrorKind::Type(message) => write!(f, "{}", message),
+            LangErrorKind::OutOfBounds(index) => write!(f, "index {} is out of bounds", index),
+            LangErrorKind::UnknownFunction(name) => write!(f, "unknown function `{}`", name),
+            LangErrorKind::Arity {
+                name,
+                expected,
+                found,
+            } => write!(f, "`{}` takes {} arguments, found {}", name, expected, found),
+        }
+    }
+}
+
+impl Error for LangError {}
+
+/// The lexer scans strings and numbers like array literals, so their errors carry over.
+impl From<ParseError> for LangError {
+    fn from(err: ParseError) -> Self {
+        let kind = match err.kind {
+            ParseErrorKind::UnexpectedEnd => LangErrorKind::UnexpectedEnd,
+            ParseErrorKind::UnexpectedChar(c) => LangErrorKind::UnexpectedChar(c),
+            ParseErrorKind::UnterminatedString => LangErrorKind::UnterminatedString,
+            ParseErrorKind::InvalidEscape(c) => LangErrorKind::InvalidEscape(c),
+            ParseErrorKind::InvalidNumber(text) => LangErrorKind::InvalidNumber(text),
+            ParseErrorKind::InvalidKeyword(word) | ParseErrorKind::UnknownWord(word) => {
+                LangErrorKind::UnexpectedToken(format!("`{}`", word))
+            }
+            ParseErrorKind::NestingTooDeep => LangErrorKind::NestingTooDeep,
+        };
+        LangError {
+            kind,
+            line: err.line,
+            column: err.column,
+        }
+    }
+}
+
+/// Runs a program in a fresh [`Interpreter`] and returns what it printed.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::lang;
+///
+/// assert_eq!(lang::run("print('hi')!").unwrap(), "hi\n");
+/// assert!(lang::run("print(nope)!").is_err());
+/// ```
+pub fn run(src: &str) -> Result<String, LangError> {
+    let mut interpreter = Interpreter::new();
+    interpreter.run(src)?;
+    Ok(interpreter.take_output())
+}
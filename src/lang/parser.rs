@@ -0,0 +1,410 @@
+//! Turns tokens into statements.
+
+use super::lexer::{Spanned, Token};
+use super::{LangError, LangErrorKind};
+use crate::syntax::Keyword;
+use crate::value::Bool;
+use crate::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Less,
+    Greater,
+    LessEq,
+    GreaterEq,
+    Eq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExprKind {
+    Literal(Value),
+    Var(String),
+    List(Vec<Expr>),
+    Index(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Expr {
+    pub kind: ExprKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum StmtKind {
+    Declare {
+        binding: Keyword,
+        contents: Keyword,
+        name: String,
+        value: Expr,
+    },
+    /// `name[i][j] = value`, with an empty `path` for plain reassignment.
+    Assign {
+        name: String,
+        path: Vec<Expr>,
+        value: Expr,
+    },
+    Expr(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Stmt {
+    pub kind: StmtKind,
+    /// Whether the statement ended in `?` and should print its value.
+    pub debug: bool,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// How deeply expressions may nest, counting brackets, parentheses, operators and indexes.
+///
+/// Parsing and evaluating recurse once per level, taking up to about 12 KiB of stack per level in a debug build,
+/// so this keeps them within the 2 MiB a spawned thread gets by default.
+const MAX_DEPTH: usize = 128;
+
+pub(crate) fn parse(tokens: Vec<Spanned>) -> Result<Vec<Stmt>, LangError> {
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        depth: 0,
+    };
+    let mut stmts = Vec::new();
+    while parser.peek().token != Token::Eof {
+        stmts.push(parser.statement()?);
+    }
+    Ok(stmts)
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    pos: usize,
+    /// How deeply the expression being parsed nests so far.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Spanned {
+        &self.tokens[self.pos]
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + offset).min(last)].token
+    }
+
+    fn bump(&mut self) -> Spanned {
+        let spanned = self.tokens[self.pos].clone();
+        if spanned.token != Token::Eof {
+            self.pos += 1;
+        }
+        spanned
+    }
+
+    fn unexpected(&self) -> LangError {
+        let spanned = self.peek();
+        let kind = match spanned.token {
+            Token::Eof => LangErrorKind::UnexpectedEnd,
+            ref token => LangErrorKind::UnexpectedToken(token.describe()),
+        };
+        LangError {
+            kind,
+            line: spanned.line,
+            column: spanned.column,
+        }
+    }
+
+    /// Goes one level deeper, failing at the next token if that is past [`MAX_DEPTH`].
+    fn enter(&mut self) -> Result<(), LangError> {
+        if self.depth == MAX_DEPTH {
+            let spanned = self.peek();
+            return Err(LangError {
+                kind: LangErrorKind::NestingTooDeep,
+                line: spanned.line,
+                column: spanned.column,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn leave(&mut self, levels: usize) {
+        self.depth -= levels;
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<Spanned, LangError> {
+        if self.peek().token == expected {
+            Ok(self.bump())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn keyword(&self, offset: usize) -> Option<Keyword> {
+        match self.peek_at(offset) {
+            Token::Ident(word) if word == "const" => Some(Keyword::Const),
+            Token::Ident(word) if word == "var" => Some(Keyword::Var),
+            _ => None,
+        }
+    }
+
+    fn statement(&mut self) -> Result<Stmt, LangError> {
+        let start = self.peek().clone();
+        let kind = match (self.keyword(0), self.keyword(1)) {
+            (Some(binding), Some(contents)) => {
+                self.bump();
+                self.bump();
+                let name = match &self.peek().token {
+                    Token::Ident(name) => name.clone(),
+                    _ => return Err(self.unexpected()),
+                };
+                self.bump();
+                self.expect(Token::Assign)?;
+                StmtKind::Declare {
+                    binding,
+                    contents,
+                    name,
+                    value: self.expr()?,
+                }
+            }
+            _ => {
+                let expr = self.expr()?;
+                if self.peek().token == Token::Assign {
+                    let (name, path) = into_target(expr)?;
+                    self.bump();
+                    StmtKind::Assign {
+                        name,
+                        path,
+                        value: self.expr()?,
+                    }
+                } else {
+                    StmtKind::Expr(expr)
+                }
+            }
+        };
+        let debug = match self.peek().token {
+            Token::Bang(_) => false,
+            Token::Question => true,
+            _ => return Err(self.unexpected()),
+        };
+        self.bump();
+        Ok(Stmt {
+            kind,
+            debug,
+            line: start.line,
+            column: start.column,
+        })
+    }
+
+    fn expr(&mut self) -> Result<Expr, LangError> {
+        self.enter()?;
+        let expr = self.comparison()?;
+        self.leave(1);
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, LangError> {
+        let left = self.additive()?;
+        let op = match self.peek().token {
+            Token::Less => BinaryOp::Less,
+            Token::Greater => BinaryOp::Greater,
+            Token::LessEq => BinaryOp::LessEq,
+            Token::GreaterEq => BinaryOp::GreaterEq,
+            Token::EqEq => BinaryOp::Eq,
+            _ => return Ok(left),
+        };
+        self.bump();
+        let right = self.additive()?;
+        Ok(binary(op, left, right))
+    }
+
+    /// Every operator in a chain nests the ones before it one level deeper.
+    fn additive(&mut self) -> Result<Expr, LangError> {
+        let mut left = self.multiplicative()?;
+        let mut levels = 0;
+        loop {
+            let op = match self.peek().token {
+                Token::Plus => BinaryOp::Add,
+                Token::Minus => BinaryOp::Sub,
+                _ => break,
+            };
+            self.enter()?;
+            levels += 1;
+            self.bump();
+            let right = self.multiplicative()?;
+            left = binary(op, left, right);
+        }
+        self.leave(levels);
+        Ok(left)
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, LangError> {
+        let mut left = self.unary()?;
+        let mut levels = 0;
+        loop {
+            let op = match self.peek().token {
+                Token::Star => BinaryOp::Mul,
+                Token::Slash => BinaryOp::Div,
+                Token::Percent => BinaryOp::Rem,
+                _ => break,
+            };
+            self.enter()?;
+            levels += 1;
+            self.bump();
+            let right = self.unary()?;
+            left = binary(op, left, right);
+        }
+        self.leave(levels);
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, LangError> {
+        if self.peek().token == Token::Minus {
+            self.enter()?;
+            let start = self.bump();
+            let operand = self.unary()?;
+            self.leave(1);
+            return Ok(Expr {
+                kind: ExprKind::Neg(Box::new(operand)),
+                line: start.line,
+                column: start.column,
+            });
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, LangError> {
+        let mut expr = self.primary()?;
+        let mut levels = 0;
+        loop {
+            match self.peek().token {
+                Token::LBracket => {
+                    self.enter()?;
+                    levels += 1;
+                    self.bump();
+                    let index = self.expr()?;
+                    self.expect(Token::RBracket)?;
+                    expr = Expr {
+                        line: expr.line,
+                        column: expr.column,
+                        kind: ExprKind::Index(Box::new(expr), Box::new(index)),
+                    };
+                }
+                Token::LParen => {
+                    let name = match &expr.kind {
+                        ExprKind::Var(name) => name.clone(),
+                        _ => return Err(self.unexpected()),
+                    };
+                    self.bump();
+                    let args = self.items(Token::RParen)?;
+                    expr = Expr {
+                        kind: ExprKind::Call(name, args),
+                        ..expr
+                    };
+                }
+                _ => break,
+            }
+        }
+        self.leave(levels);
+        Ok(expr)
+    }
+
+    /// Parses comma separated expressions up to and including `close`.
+    fn items(&mut self, close: Token) -> Result<Vec<Expr>, LangError> {
+        let mut items = Vec::new();
+        loop {
+            if self.peek().token == close {
+                self.bump();
+                return Ok(items);
+            }
+            items.push(self.expr()?);
+            if self.peek().token == Token::Comma {
+                self.bump();
+            } else {
+                self.expect(close)?;
+                return Ok(items);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, LangError> {
+        let start = self.peek().clone();
+        let kind = match start.token {
+            Token::Number(number) => {
+                self.bump();
+                ExprKind::Literal(Value::Number(number))
+            }
+            Token::Str(string) => {
+                self.bump();
+                ExprKind::Literal(Value::String(string))
+            }
+            Token::Ident(name) => {
+                self.bump();
+                match name.as_str() {
+                    "true" => ExprKind::Literal(Value::Bool(Bool::True)),
+                    "false" => ExprKind::Literal(Value::Bool(Bool::False)),
+                    "maybe" => ExprKind::Literal(Value::Bool(Bool::Maybe)),
+                    "undefined" => ExprKind::Literal(Value::Undefined),
+                    _ => ExprKind::Var(name),
+                }
+            }
+            Token::LBracket => {
+                self.bump();
+                ExprKind::List(self.items(Token::RBracket)?)
+            }
+            Token::LParen => {
+                self.bump();
+                let inner = self.expr()?;
+                self.expect(Token::RParen)?;
+                return Ok(inner);
+            }
+            _ => return Err(self.unexpected()),
+        };
+        Ok(Expr {
+            kind,
+            line: start.line,
+            column: start.column,
+        })
+    }
+}
+
+fn binary(op: BinaryOp, left: Expr, right: Expr) -> Expr {
+    Expr {
+        line: left.line,
+        column: left.column,
+        kind: ExprKind::Binary(op, Box::new(left), Box::new(right)),
+    }
+}
+
+/// Splits `name[i][j]` into the name and its index expressions.
+fn into_target(expr: Expr) -> Result<(String, Vec<Expr>), LangError> {
+    let (line, column) = (expr.line, expr.column);
+    let mut path = Vec::new();
+    let mut current = expr;
+    loop {
+        match current.kind {
+            ExprKind::Var(name) => {
+                path.reverse();
+                return Ok((name, path));
+            }
+            ExprKind::Index(inner, index) => {
+                path.push(*index);
+                current = *inner;
+            }
+            _ => {
+                return Err(LangError {
+                    kind: LangErrorKind::InvalidAssignment,
+                    line,
+                    column,
+                })
+            }
+        }
+    }
+}
@@ -5,7 +5,6 @@ An actual implementation of the DreamBerd array in rust.
 ## Roadmap
 
 - Currently, all failures are silent - I could change that.
-- Add `remove()` for proper mutability.
 - `pop()` and `get()` work from different ends at the moment. Could be confusing.
 
 ## Features
@@ -15,6 +14,29 @@ An actual implementation of the DreamBerd array in rust.
     - Just like answering "Bowl last, rest does not matter." to the question "What comes first, cereal or milk?", it puts an end to the "Lists start at 0" vs "Lists start at 1" argument.
 - Only floats are valid indexes.
     - Ever wanted to just `insert()` at `0.5` to put something in the middle? Now you can!
+- Every node of a `List` holds up to 16 elements, so `get`, `len` and `iter` skip through the list a chunk at a time.
+- `List::with_capacity` and `reserve` keep freed nodes around for reuse, so churn-heavy lists stop allocating.
+    - `cargo bench --bench pool` prints the allocation counts with and without the pool.
+- `Value`, an untyped DreamBerd value, so a single `List<Value>` can mix numbers, strings, `maybe`s and nested lists.
+- Parse and print DreamBerd array literals like `const const scores = [3, 2, 5]!` with `dreamberd_array::syntax`.
+- `ConstConst`, `ConstVar`, `VarConst` and `VarVar` wrap a `List` so that changing a `const` list or calling `reassign` on a `const` binding fails to compile.
+- `ExpiringList`, whose elements expire after a number of operations or a duration, like DreamBerd's `<2>` and `<20s>` lifetimes.
+- `HistoryList`, which remembers the `previous` values of every index, with a configurable retention.
+- `UndoableList`, with undo, redo and grouped changes, that works without `T: Clone`.
+- `list.transaction(|tx| ...)` applies several edits all at once, or rolls them back on `Err` or a panic.
+- `PList`, a persistent list whose versions share their unchanged nodes through `Rc` or `Arc`.
+- `FixedList<T, N>`, a list with room for `N` elements that never allocates and returns a `CapacityError` when full.
+- `AtomicList`, a list that many threads can push to and pop from without locks, with snapshots that stay consistent.
+- `ConcurrentList`, which many threads can `get`, `insert` and `remove` at fractional indexes at once.
+- Async `Stream` and `Sink` adapters and a bounded `async_channel` in `dreamberd_array::stream`, using only std futures.
+- `ListQueue`, a blocking work queue for many threads with `send`, `recv`, `recv_timeout`, `close`, a limit and FIFO or LIFO order.
+- Run small DreamBerd programs such as `xs[i + 0.5] = 42!` against `List<Value>` with `dreamberd_array::lang`.
+- Dependency free binary encoding in `dreamberd_array::encode`, with a versioned header and checksum.
+- Optional `serde` support behind the `serde` feature.
+    - Lists are written as a plain sequence, or as a map keyed by index with `dreamberd_array::serde::indexed`.
+- Optional `par_iter`, `par_iter_mut` and `par_sort` on scoped threads behind the `parallel` feature, without extra dependencies.
+- Works under `#![no_std]` with `alloc` when the default `std` feature is turned off.
+    - `List`, its iterators, indexing, `dlist!`, `encode`, `serde` and the wrapper lists stay available; the threaded, timed and interpreter modules need `std`.
 
 ## Usage
 First add the crate to your project.
@@ -52,6 +74,7 @@ for entry in list.iter() {
 ```
 
 ```rust
+# use dreamberd_array::List;
 let input: Vec<usize> = vec![1, 2, 3];
 let mut list = List::new();
 for i in input {
@@ -73,46 +96,247 @@ for (i, element) in list.into_iter().enumerate() {
 }
 ```
 
+Lists can also be built from and turned back into the standard collections.
+The first element always ends up at index -1.
+```rust
+# use dreamberd_array::List;
+let list = List::from(vec![1, 2, 3]);
+assert_eq!(list[-1.0], 1);
+assert_eq!(list[1.0], 3);
+
+for entry in &list {
+    println!("{}", entry);
+}
+
+let vec: Vec<usize> = list.into();
+assert_eq!(vec, vec![1, 2, 3]);
+```
+
+The `dlist!` macro writes the same thing down in index order, with optional fractional insertions after a `;`.
+```rust
+use dreamberd_array::dlist;
+
+let list = dlist![1, 2, 3; 0.5 => 42];
+assert_eq!(list[-1.0], 1);
+assert_eq!(list[1.0], 42);
+```
+
 For more examples, check out the documentation of the `List` struct and its methods.
 */
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{self, Rev};
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+use fixed::FixedList;
+
+#[cfg(feature = "std")]
+pub mod atomic;
+#[cfg(feature = "std")]
+pub mod concurrent;
+pub mod encode;
+#[cfg(feature = "std")]
+pub mod expiring;
+pub mod fixed;
+pub mod history;
+#[cfg(feature = "std")]
+pub mod lang;
+pub mod mutability;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod persistent;
+#[cfg(feature = "std")]
+pub mod queue;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+pub mod repl;
+#[cfg(feature = "std")]
+pub mod stream;
+#[cfg(feature = "std")]
+pub mod syntax;
+pub mod transaction;
+pub mod undo;
+#[cfg(feature = "std")]
+pub mod value;
+
+pub use mutability::{ConstConst, ConstVar, VarConst, VarVar};
+#[cfg(feature = "std")]
+pub use value::Value;
 
+/// Rounds up to a whole number, like `f32::ceil`, which is not available in `core`.
+pub(crate) fn ceil(x: f32) -> f32 {
+    // Every float at least this large is already whole, and NaN falls through too.
+    if !(-8_388_608.0..8_388_608.0).contains(&x) {
+        return x;
+    }
+    let whole = x as i32 as f32;
+    if whole < x {
+        whole + 1.0
+    } else {
+        whole
+    }
+}
 
-use std::ops::{Index, IndexMut};
+/// Returns how far the element an index refers to is from index -1, in a list of `len` elements.
+///
+/// Fractional indexes round up, so this is the element `List::get` returns.
+pub(crate) fn position(index: f32, len: usize) -> Option<usize> {
+    if !(-1.0..=len as f32 - 2.0).contains(&index) {
+        return None;
+    }
+    Some((ceil(index) + 1.0) as usize)
+}
+
+/// The most elements a single node of a `List` holds.
+const CHUNK: usize = 16;
 
-/// `List` is a simple linked list, designed after the DreamBerd array.
+/// `List` is an unrolled linked list, designed after the DreamBerd array.
 ///
 /// The index starts at -1 and increases by 1 for each element.
 /// All indexing is floating-point based. This means you can finally `insert` at `0.5`!
 ///
+/// Every node holds up to 16 elements, so walking to an index skips them a chunk at a time.
+///
 /// It supports any type.
-#[derive(Debug)]
 pub struct List<T> {
     head: Link<T>,
+    /// The number of elements, kept up to date so that counting them does not walk the nodes.
+    len: usize,
+    pool: Pool<T>,
 }
 
 type Link<T> = Option<Box<Node<T>>>;
 
-#[derive(Debug)]
+/// A chunk of elements, starting with the lowest index. The nodes after it hold lower indexes.
+///
+/// Only an empty list has no nodes, a node in the list is never empty.
 struct Node<T> {
-    elem: T,
+    elems: FixedList<T, CHUNK>,
     next: Link<T>,
 }
 
-/* impl<T: Default> Default for Node<T> {
-    fn default() -> Self {
-        Node {
-            elem: Default::default(),
-            next: None,
+/// Empty nodes kept around by `List::with_capacity` and `List::reserve`.
+struct Pool<T> {
+    spare: Vec<Box<Node<T>>>,
+    /// How many spare nodes to keep when elements are removed.
+    keep: usize,
+    /// How many elements the list was asked to make room for.
+    reserved: usize,
+}
+
+impl<T> Pool<T> {
+    /// Returns an empty node, reusing a spare one if there is one.
+    fn node(&mut self, next: Link<T>) -> Box<Node<T>> {
+        match self.spare.pop() {
+            Some(mut node) => {
+                node.next = next;
+                node
+            }
+            None => Box::new(Node {
+                elems: FixedList::new(),
+                next,
+            }),
+        }
+    }
+
+    /// Takes an empty node out of the list, keeping it if the pool is not full.
+    fn release(&mut self, mut node: Box<Node<T>>) -> Link<T> {
+        let next = node.next.take();
+        if self.spare.len() < self.keep {
+            self.spare.push(node);
+        }
+        next
+    }
+
+    /// Merges the node at `link` into the one after it, if their elements fit into one node.
+    fn merge(&mut self, link: &mut Link<T>) {
+        let Some(upper) = link.as_mut() else {
+            return;
+        };
+        let fits = match &upper.next {
+            Some(lower) => upper.elems.len() + lower.elems.len() <= CHUNK,
+            None => upper.elems.is_empty(),
+        };
+        if !fits {
+            return;
+        }
+        let Some(mut upper) = link.take() else {
+            return;
+        };
+        if let Some(lower) = upper.next.as_mut() {
+            let count = upper.elems.len();
+            upper.elems.move_front_to(count, &mut lower.elems);
         }
+        *link = self.release(upper);
     }
-} */
+}
+
+/// Returns the link to the node `k` nodes after the first one.
+fn nth_link<T>(mut link: &mut Link<T>, k: usize) -> &mut Link<T> {
+    for _ in 0..k {
+        link = &mut link.as_mut().expect("the node exists").next;
+    }
+    link
+}
+
+/// Creates a `List` from its elements, starting at index -1.
+///
+/// `dlist![a, b, c]` is the same as `List::from([a, b, c])`, so the literal reads in index order.
+///
+/// After a `;`, `index => value` pairs are passed to `List::insert` one after the other.
+/// Every insertion sees the list as left by the previous one, and insertions into an empty list are ignored.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::dlist;
+///
+/// let list = dlist![1, 2, 3];
+/// assert_eq!(list.get(-1.0), Some(&1));
+/// assert_eq!(list.get(0.0), Some(&2));
+/// assert_eq!(list.get(1.0), Some(&3));
+///
+/// let list = dlist![1, 2, 3; 0.5 => 42];
+/// assert_eq!(list.get(0.5), Some(&42));
+/// assert_eq!(list.get(1.0), Some(&42));
+/// assert_eq!(list.get(2.0), Some(&3));
+///
+/// let list = dlist![1, 2, 3; -0.5 => 42, 1.5 => 69];
+/// assert_eq!(list.get(-0.5), Some(&42));
+/// assert_eq!(list.get(1.5), Some(&69));
+/// ```
+#[macro_export]
+macro_rules! dlist {
+    () => {
+        $crate::List::new()
+    };
+    ($($elem:expr),+ $(,)?) => {
+        $crate::List::from([$($elem),+])
+    };
+    ($($elem:expr),* ; $($index:expr => $value:expr),+ $(,)?) => {{
+        let mut list = $crate::List::from([$($elem),*]);
+        $(list.insert($index, $value);)+
+        list
+    }};
+}
 
 pub struct IntoIter<T>(List<T>);
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
+    elems: Rev<slice::Iter<'a, T>>,
 }
 pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
+    elems: Rev<slice::IterMut<'a, T>>,
 }
 
 impl<T> List<T> {
@@ -126,7 +350,118 @@ impl<T> List<T> {
     /// assert_eq!(list.len(), 0);
     /// ```
     pub fn new() -> Self {
-        List { head: None }
+        List {
+            head: None,
+            len: 0,
+            pool: Pool {
+                spare: Vec::new(),
+                keep: 0,
+                reserved: 0,
+            },
+        }
+    }
+
+    /// Creates an empty `List` that can hold `capacity` elements before allocating again.
+    ///
+    /// Nodes freed by `pop` and `remove` are kept for reuse, as many as the reserved elements need.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::List;
+    ///
+    /// let mut list = List::with_capacity(2);
+    /// assert_eq!(list.capacity(), 2);
+    ///
+    /// list.push(1);
+    /// list.push(2);
+    /// list.push(3);
+    /// assert_eq!(list.capacity(), 3);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut list = List::new();
+        list.reserve(capacity);
+        list
+    }
+
+    /// Returns how many elements the list has room for: what was reserved, or its length if that is more.
+    ///
+    /// Pushing up to the capacity does not allocate.
+    /// An `insert` into a full node splits it, which the reservation leaves room for once.
+    /// Popping elements does not lower the capacity, since the freed nodes are kept:
+    /// it stays at the highest reservation until [`List::shrink_to_fit`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::List;
+    ///
+    /// let mut list = List::with_capacity(4);
+    /// list.push(1);
+    ///
+    /// assert_eq!(list.len(), 1);
+    /// assert_eq!(list.capacity(), 4);
+    ///
+    /// list.pop();
+    /// assert_eq!(list.capacity(), 4);
+    /// list.shrink_to_fit();
+    /// assert_eq!(list.capacity(), 0);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.len().max(self.pool.reserved)
+    }
+
+    /// Makes room for at least `additional` more elements without allocating.
+    ///
+    /// From then on, nodes freed by `pop` and `remove` are kept for reuse,
+    /// so the list can shrink and grow back to that size without allocating.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::List;
+    ///
+    /// let mut list = List::from([1, 2]);
+    /// list.reserve(3);
+    /// assert_eq!(list.capacity(), 5);
+    ///
+    /// list.push(3);
+    /// list.pop();
+    /// assert_eq!(list.capacity(), 5);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        if additional == 0 {
+            return;
+        }
+        let len = self.len();
+        let room = self.head.as_ref().map_or(0, |node| CHUNK - node.elems.len());
+        // One node more than the pushes need, for an insert that splits a full one.
+        let nodes = additional.saturating_sub(room).div_ceil(CHUNK) + 1;
+        let pool = &mut self.pool;
+        pool.reserved = pool.reserved.max(len + additional);
+        pool.keep = pool.keep.max(nodes);
+        pool.spare.reserve_exact(pool.keep - pool.spare.len());
+        while pool.spare.len() < nodes {
+            pool.spare.push(Box::new(Node {
+                elems: FixedList::new(),
+                next: None,
+            }));
+        }
+    }
+
+    /// Frees every spare node, so the capacity matches the length.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::List;
+    ///
+    /// let mut list = List::with_capacity(10);
+    /// list.push(1);
+    /// list.shrink_to_fit();
+    ///
+    /// assert_eq!(list.capacity(), 1);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.pool.spare = Vec::new();
+        self.pool.keep = 0;
+        self.pool.reserved = 0;
     }
 
     /// Pushes an element to the front of the list.
@@ -141,11 +476,15 @@ impl<T> List<T> {
     /// assert_eq!(list.len(), 1);
     /// ```
     pub fn push(&mut self, elem: T) {
-        let new_node = Box::new(Node {
-            elem,
-            next: self.head.take(),
-        });
-        self.head = Some(new_node);
+        if self.head.as_ref().is_none_or(|node| node.elems.is_full()) {
+            let new_node = self.pool.node(self.head.take());
+            self.head = Some(new_node);
+        }
+        if let Some(node) = self.head.as_mut() {
+            let top = node.elems.len();
+            node.elems.insert_at(top, elem);
+            self.len += 1;
+        }
     }
 
     /// Removes and returns the element from the front of the list.
@@ -166,10 +505,13 @@ impl<T> List<T> {
     /// assert_eq!(list.pop(), None);
     /// ```
     pub fn pop(&mut self) -> Option<T> {
-        self.head.take().map(|node| {
-            self.head = node.next;
-            node.elem
-        })
+        let node = self.head.as_mut()?;
+        let elem = node.elems.pop()?;
+        self.len -= 1;
+        if node.elems.is_empty() {
+            self.pool.merge(&mut self.head);
+        }
+        Some(elem)
     }
 
     /// Returns a reference to the first element in the list.
@@ -184,7 +526,7 @@ impl<T> List<T> {
     /// assert_eq!(list.peek(), Some(&1));
     /// ```
     pub fn peek(&self) -> Option<&T> {
-        self.head.as_ref().map(|node| &node.elem)
+        self.head.as_ref().and_then(|node| node.elems.peek())
     }
 
     /// Returns a mutable reference to the first element in the list.
@@ -205,30 +547,7 @@ impl<T> List<T> {
     /// assert_eq!(list.peek(), Some(&42));
     /// ```
     pub fn peek_mut(&mut self) -> Option<&mut T> {
-        self.head.as_mut().map(|node| {
-            &mut node.elem
-        })
-    }
-
-    /// Consumes the list and returns an iterator over it.
-    ///
-    /// # Example
-    /// ```
-    /// use dreamberd_array::List;
-    ///
-    /// let mut list = List::new();
-    /// list.push(1);
-    /// list.push(2);
-    /// list.push(3);
-    ///
-    /// let mut iter = list.into_iter();
-    /// assert_eq!(iter.next(), Some(3));
-    /// assert_eq!(iter.next(), Some(2));
-    /// assert_eq!(iter.next(), Some(1));
-    /// assert_eq!(iter.next(), None);
-    /// ```
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+        self.head.as_mut().and_then(|node| node.elems.peek_mut())
     }
 
     /// Returns an iterator over the list.
@@ -248,9 +567,10 @@ impl<T> List<T> {
     /// assert_eq!(iter.next(), Some(&1));
     /// assert_eq!(iter.next(), None);
     /// ```
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.head.as_deref(),
+            elems: [].iter().rev(),
         }
     }
 
@@ -271,8 +591,11 @@ impl<T> List<T> {
     /// assert_eq!(iter.next(), Some(&mut 1));
     /// assert_eq!(iter.next(), None);
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        IterMut { next: self.head.as_deref_mut() }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+            elems: [].iter_mut().rev(),
+        }
     }
 
     /// Returns the number of elements in the list.
@@ -289,13 +612,7 @@ impl<T> List<T> {
     /// assert_eq!(list.len(), 3);
     /// ```
     pub fn len(&self) -> usize {
-        let mut count = 0;
-        let mut cur_link = self.head.as_deref();
-        while let Some(node) = cur_link {
-            count += 1;
-            cur_link = node.next.as_deref();
-        }
-        count
+        self.len
     }
 
     /// Returns whether the list is empty.
@@ -334,25 +651,10 @@ impl<T> List<T> {
     /// assert_eq!(list.get(2.0), None);
     /// ```
     pub fn get(&self, index: f32) -> Option<&T> {
-        let mut count = self.len() as f32 - 2.0;
-        let mut cur_link = self.head.as_deref();
-        if index < -1.0 {
-            return None;
-        } else if index - count > 0.0 {
-            return None;
-        }
-        let mut index2 = index;
-        if index.fract() != 0.0 {
-            index2 += 1.0;
-        }
-        while let Some(node) = cur_link {
-            if count <= index2 {
-                return Some(&node.elem);
-            }
-            count -= 1.0;
-            cur_link = node.next.as_deref();
-        }
-        None
+        let (k, offset) = self.locate(index)?;
+        self.nodes()
+            .nth(k)
+            .map(|node| &node.elems.as_slice()[offset])
     }
 
     /// Returns the element at the given index.
@@ -374,25 +676,10 @@ impl<T> List<T> {
     /// assert_eq!(list.get_mut(2.0), None);
     /// ```
     pub fn get_mut(&mut self, index: f32) -> Option<&mut T> {
-        let mut count = self.len() as f32 - 2.0;
-        let mut cur_link = self.head.as_deref_mut();
-        if index < -1.0 {
-            return None;
-        } else if index - count > 0.0 {
-            return None;
-        }
-        let mut index2 = index;
-        if index.fract() != 0.0 {
-            index2 += 1.0;
-        }
-        while let Some(node) = cur_link {
-            if count <= index2 {
-                return Some(&mut node.elem);
-            }
-            count -= 1.0;
-            cur_link = node.next.as_deref_mut();
-        }
-        None
+        let (k, offset) = self.locate(index)?;
+        nth_link(&mut self.head, k)
+            .as_mut()
+            .map(|node| &mut node.elems.as_mut_slice()[offset])
     }
 
     /// Inserts an element at the given index.
@@ -419,65 +706,82 @@ impl<T> List<T> {
     /// assert_eq!(list.get(2.0), Some(&3));
     /// ```
     pub fn insert(&mut self, index: f32, elem: T) {
-        let mut count = self.len() as f32 - 2.0;
-        let mut cur_link = self.head.as_deref_mut();
-        if index < -1.0 {
+        let Some((k, offset)) = self.locate(index) else {
             return;
-        } else if index - count > 0.0 {
+        };
+        let Some(node) = nth_link(&mut self.head, k).as_mut() else {
+            return;
+        };
+        self.len += 1;
+        if !node.elems.is_full() {
+            node.elems.insert_at(offset, elem);
             return;
         }
-        let mut index2 = index;
-        if index.fract() != 0.0 {
-            index2 += 1.0;
-        }
-        while let Some(node) = cur_link {
-            if count <= index2 {
-                let new_node = Box::new(Node {
-                    elem,
-                    next: node.next.take(),
-                });
-                node.next = Some(new_node);
-                return;
-            }
-            count -= 1.0;
-            cur_link = node.next.as_deref_mut();
+        // Split the full node, moving its lower half into a new node after it.
+        let half = CHUNK / 2;
+        let mut lower = self.pool.node(node.next.take());
+        node.elems.move_front_to(half, &mut lower.elems);
+        if offset < half {
+            lower.elems.insert_at(offset, elem);
+        } else {
+            node.elems.insert_at(offset - half, elem);
         }
+        node.next = Some(lower);
     }
-}
 
-// TODO: Below:
-// Does not work, and I am really lacking the knwoldge of lifetimes and generics to make this work. I think.
-/* 
-impl<'a, T: Clone + Default> List<T> {
-    pub fn remove(&'a mut self, index: f32) -> Option<T> {
-        let mut count = self.len() as f32 - 2.0;
-        let mut cur_link = self.head.as_deref_mut();
-        if index < -1.0 {
-            return None;
-        } else if index - count > 0.0 {
-            return None;
+    /// Removes and returns the element at the given index.
+    ///
+    /// The index starts with -1 at the end of the list and is in fractional form.
+    /// Fractional indexes remove the same element `get` would return.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::List;
+    ///
+    /// let mut list = List::new();
+    /// list.push(1);
+    /// list.push(2);
+    /// list.push(3);
+    ///
+    /// assert_eq!(list.remove(0.0), Some(2));
+    /// assert_eq!(list.len(), 2);
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// assert_eq!(list.get(0.0), Some(&3));
+    /// assert_eq!(list.remove(-0.5), Some(3));
+    /// assert_eq!(list.remove(1.0), None);
+    /// ```
+    pub fn remove(&mut self, index: f32) -> Option<T> {
+        let (k, offset) = self.locate(index)?;
+        let link = nth_link(&mut self.head, k);
+        let elem = link.as_mut()?.elems.remove_at(offset);
+        self.len -= 1;
+        // Keep the nodes from running empty, merging with the neighbours where they fit.
+        self.pool.merge(link);
+        if k > 0 {
+            self.pool.merge(nth_link(&mut self.head, k - 1));
         }
-        let mut new_list = List::new();
-        let mut new_node: Node<T> = Default::default();
-        let mut out = None;
-        while let Some(node) = cur_link {
-            if count <= index {
-                new_list.head = node.next.take();
-                out = Some(node.elem.clone());
-            } else {
-                new_list.push(node.elem.clone());
-                new_node = Node {
-                    elem: node.elem.clone(),
-                    next: node.next.take(),
-                };
+        Some(elem)
+    }
+
+    /// Returns the nodes, starting at the front.
+    fn nodes(&self) -> impl Iterator<Item = &Node<T>> {
+        iter::successors(self.head.as_deref(), |node| node.next.as_deref())
+    }
+
+    /// Returns which node an index falls into, counted from the front, and where in the node it is.
+    fn locate(&self, index: f32) -> Option<(usize, usize)> {
+        let pos = position(index, self.len)?;
+        let mut top = self.len;
+        for (k, node) in self.nodes().enumerate() {
+            let bottom = top - node.elems.len();
+            if pos >= bottom {
+                return Some((k, pos - bottom));
             }
-            count -= 1.0;
-            cur_link = node.next.as_deref_mut();
+            top = bottom;
         }
-        self.head = Some(Box::new(new_node));
-        out
+        None
     }
-} */
+}
 
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
@@ -488,6 +792,347 @@ impl<T> Drop for List<T> {
     }
 }
 
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl<T> List<T> {
+    /// Returns an iterator over the elements starting at index -1, without allocating.
+    fn index_order(&self) -> IndexOrder<'_, T> {
+        let mut order = IndexOrder {
+            runs: [(None, 0); RUNS],
+            depth: 0,
+            elems: [].iter(),
+            remaining: self.len(),
+        };
+        let nodes = self.nodes().count();
+        if nodes > 0 {
+            order.runs[0] = (self.head.as_deref(), nodes);
+            order.depth = 1;
+        }
+        order
+    }
+}
+
+/// Room for the runs `IndexOrder` has yet to visit, one per halving and the one being split.
+const RUNS: usize = usize::BITS as usize + 2;
+
+/// Walks the nodes back to front, so the elements come out starting at index -1.
+///
+/// The nodes only link towards index -1, so the remaining nodes are split into runs:
+/// the later half of a run is visited before its first half, which takes one walk per halving
+/// and keeps the stack of runs small enough for an array.
+struct IndexOrder<'a, T> {
+    /// Runs of nodes still to visit, the last one first: where each starts and how many nodes it spans.
+    runs: [(Option<&'a Node<T>>, usize); RUNS],
+    depth: usize,
+    elems: slice::Iter<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for IndexOrder<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(elem) = self.elems.next() {
+                self.remaining -= 1;
+                return Some(elem);
+            }
+            if self.depth == 0 {
+                return None;
+            }
+            self.depth -= 1;
+            let (first, count) = self.runs[self.depth];
+            let first = first?;
+            if count == 1 {
+                self.elems = first.elems.as_slice().iter();
+                continue;
+            }
+            let half = count / 2;
+            let later = iter::successors(Some(first), |node| node.next.as_deref()).nth(half);
+            self.runs[self.depth] = (Some(first), half);
+            self.runs[self.depth + 1] = (later, count - half);
+            self.depth += 2;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IndexOrder<'_, T> {}
+
+/// Cloning walks the list once and appends to the tail, so long lists do not recurse.
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        let mut new_list = List::new();
+        let mut tail = &mut new_list.head;
+        for node in self.nodes() {
+            let node = tail.insert(Box::new(Node {
+                elems: node.elems.clone(),
+                next: None,
+            }));
+            tail = &mut node.next;
+        }
+        new_list.len = self.len;
+        new_list
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+/// Hashes the length and then every element from index -1 up, so equal lists hash equally.
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let elems = self.index_order();
+        state.write_usize(elems.len());
+        for elem in elems {
+            elem.hash(state);
+        }
+    }
+}
+
+/// Lists are compared lexicographically, starting at index -1.
+impl<T: PartialOrd> PartialOrd for List<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.index_order().partial_cmp(other.index_order())
+    }
+}
+
+impl<T: Ord> Ord for List<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index_order().cmp(other.index_order())
+    }
+}
+
+/// Formats the elements like a slice, starting at index -1.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let list = List::from(vec![1, 2, 3]);
+///
+/// assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+/// ```
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.index_order()).finish()
+    }
+}
+
+/// Formats the elements like a slice, starting at index -1.
+///
+/// The alternate form `{:#}` prints one element per line, prefixed with its index.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let list = List::from(vec!["a", "b", "c"]);
+///
+/// assert_eq!(format!("{}", list), "[a, b, c]");
+/// assert_eq!(format!("{:#}", list), "-1: a\n0: b\n1: c");
+/// ```
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            for (i, elem) in self.index_order().enumerate() {
+                if i > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "{}: {}", i as isize - 1, elem)?;
+            }
+            Ok(())
+        } else {
+            write!(f, "[")?;
+            for (i, elem) in self.index_order().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", elem)?;
+            }
+            write!(f, "]")
+        }
+    }
+}
+
+/// Collects an iterator by pushing every element in turn.
+///
+/// The first element ends up at index -1, the last one at the front of the list.
+/// As `iter()` starts at the front, `list.iter().cloned().collect()` reverses the list.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let list: List<usize> = (1..=3).collect();
+///
+/// assert_eq!(list[-1.0], 1);
+/// assert_eq!(list[0.0], 2);
+/// assert_eq!(list[1.0], 3);
+/// assert_eq!(list.peek(), Some(&3));
+/// ```
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Pushes every element in turn, so the last element ends up at the front of the list.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let mut list = List::from([1, 2]);
+/// list.extend(vec![3, 4]);
+///
+/// assert_eq!(list[-1.0], 1);
+/// assert_eq!(list[2.0], 4);
+/// ```
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for List<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// Builds a list whose index -1 is the first element of the `Vec`.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let list = List::from(vec![1, 2, 3]);
+///
+/// assert_eq!(list[-1.0], 1);
+/// assert_eq!(list[1.0], 3);
+/// ```
+impl<T> From<Vec<T>> for List<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+/// Builds a list whose index -1 is the first element of the array.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let list = List::from([1, 2, 3]);
+///
+/// assert_eq!(list[-1.0], 1);
+/// assert_eq!(list[1.0], 3);
+/// ```
+impl<T, const N: usize> From<[T; N]> for List<T> {
+    fn from(array: [T; N]) -> Self {
+        array.into_iter().collect()
+    }
+}
+
+/// Returns the elements starting at index -1, the reverse of `From<Vec<T>>`.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let list = List::from(vec![1, 2, 3]);
+/// let vec: Vec<usize> = list.into();
+///
+/// assert_eq!(vec, vec![1, 2, 3]);
+/// ```
+impl<T> From<List<T>> for Vec<T> {
+    fn from(list: List<T>) -> Self {
+        let mut out: Vec<T> = list.into_iter().collect();
+        out.reverse();
+        out
+    }
+}
+
+/// Returns the elements starting at index -1 at the front of the deque.
+///
+/// # Example
+/// ```
+/// use std::collections::VecDeque;
+/// use dreamberd_array::List;
+///
+/// let list = List::from(vec![1, 2, 3]);
+/// let deque: VecDeque<usize> = list.into();
+///
+/// assert_eq!(deque.front(), Some(&1));
+/// assert_eq!(deque.back(), Some(&3));
+/// ```
+impl<T> From<List<T>> for VecDeque<T> {
+    fn from(list: List<T>) -> Self {
+        let mut out = VecDeque::new();
+        for elem in list {
+            out.push_front(elem);
+        }
+        out
+    }
+}
+
+/// Consumes the list and returns an iterator over it, starting at the front.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let mut list = List::new();
+/// list.push(1);
+/// list.push(2);
+/// list.push(3);
+///
+/// let mut iter = list.into_iter();
+/// assert_eq!(iter.next(), Some(3));
+/// assert_eq!(iter.next(), Some(2));
+/// assert_eq!(iter.next(), Some(1));
+/// assert_eq!(iter.next(), None);
+/// ```
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
@@ -498,27 +1143,35 @@ impl<T> Iterator for IntoIter<T> {
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.map(|node| {
+        loop {
+            if let Some(elem) = self.elems.next() {
+                return Some(elem);
+            }
+            let node = self.next?;
+            self.elems = node.elems.as_slice().iter().rev();
             self.next = node.next.as_deref();
-            &node.elem
-        })
+        }
     }
 }
 
 impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.take().map(|node| {
-            self.next = node.next.as_deref_mut();
-            &mut node.elem
-        })
+        loop {
+            if let Some(elem) = self.elems.next() {
+                return Some(elem);
+            }
+            let Node { elems, next } = self.next.take()?;
+            self.elems = elems.as_mut_slice().iter_mut().rev();
+            self.next = next.as_deref_mut();
+        }
     }
 }
 
 impl<T> Index<f32> for List<T> {
     type Output = T;
     fn index(&self, index: f32) -> &Self::Output {
-        &self.get(index).unwrap()
+        self.get(index).unwrap()
     }
 }
 
@@ -0,0 +1,82 @@
+//! An interactive shell for exploring DreamBerd list indexing.
+//!
+//! Run it without arguments for a prompt, or pass a script file to replay it.
+//! See `dreamberd_array::repl` for the commands.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use dreamberd_array::repl::Session;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => interactive(),
+        [path] => replay(path),
+        _ => {
+            eprintln!("usage: dreamberd-array [SCRIPT]");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn interactive() -> ExitCode {
+    let mut session = Session::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush().ok();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(err)) => {
+                eprintln!("error: {}", err);
+                return ExitCode::FAILURE;
+            }
+            None => {
+                println!();
+                return ExitCode::SUCCESS;
+            }
+        };
+        if matches!(line.trim(), "quit" | "exit") {
+            return ExitCode::SUCCESS;
+        }
+        match session.execute(&line) {
+            Ok(out) if out.is_empty() => {}
+            Ok(out) => println!("{}", out),
+            Err(err) => println!("error: {}", err),
+        }
+    }
+}
+
+/// Runs every line of the script, echoing each command before its output.
+///
+/// Stops at the first failing command.
+fn replay(path: &str) -> ExitCode {
+    let script = match fs::read_to_string(path) {
+        Ok(script) => script,
+        Err(err) => {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut session = Session::new();
+    for (i, line) in script.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        println!("> {}", trimmed);
+        match session.execute(trimmed) {
+            Ok(out) if out.is_empty() => {}
+            Ok(out) => println!("{}", out),
+            Err(err) => {
+                eprintln!("{}:{}: {}", path, i + 1, err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
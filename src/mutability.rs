@@ -0,0 +1,412 @@
+//! Lists with DreamBerd's four kinds of declarations.
+//!
+//! In DreamBerd, `const const scores = [3, 2, 5]!` can neither be reassigned nor changed,
+//! `const var` can be changed but not reassigned, `var const` can be reassigned but not changed,
+//! and `var var` can be both.
+//! [`Binding`] wraps a [`List`] and only offers the methods its mode allows,
+//! so changing a `const` list is a compile error instead of a surprise.
+//!
+//! | Type              | `reassign` | `push`, `pop`, `insert`, `remove`, `get_mut`, `IndexMut` |
+//! |-------------------|------------|-----------------------------------------------------------|
+//! | [`ConstConst<T>`] | no         | no                                                        |
+//! | [`ConstVar<T>`]   | no         | yes                                                       |
+//! | [`VarConst<T>`]   | yes        | no                                                        |
+//! | [`VarVar<T>`]     | yes        | yes                                                       |
+//!
+//! All of them read like a `List`, since they dereference to `&List<T>`.
+//! A mode can always be tightened with [`Binding::freeze`] and [`Binding::fix`], which cost nothing.
+//! Loosening it means taking the list out with [`Binding::into_inner`] and wrapping it again.
+//!
+//! The modes decide which methods a binding has. Whether the variable holding it can be assigned a new
+//! binding is up to `let` and `let mut`, as for any other Rust value.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::{dlist, ConstConst, ConstVar};
+//!
+//! let mut scores = ConstVar::new(dlist![3, 2]);
+//! scores.push(5);
+//! scores[-1.0] = 4;
+//!
+//! let scores: ConstConst<i32> = scores.freeze();
+//! assert_eq!(scores.get(1.0), Some(&5));
+//! assert_eq!(Vec::from(scores.into_inner()), vec![4, 2, 5]);
+//! ```
+//!
+//! Changing a `const const` list does not compile:
+//! ```compile_fail
+//! use dreamberd_array::{dlist, ConstConst};
+//!
+//! let mut scores = ConstConst::new(dlist![3, 2, 5]);
+//! scores.push(1);
+//! ```
+//!
+//! Neither does reassigning a `const var` one:
+//! ```compile_fail
+//! use dreamberd_array::{dlist, ConstVar};
+//!
+//! let mut scores = ConstVar::new(dlist![3, 2, 5]);
+//! scores.reassign(dlist![1]);
+//! ```
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, Index, IndexMut};
+
+use crate::{IntoIter, Iter, IterMut, List};
+
+/// Marks a binding or its contents as unchangeable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Const {}
+
+/// Marks a binding or its contents as changeable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Var {}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Const {}
+    impl Sealed for super::Var {}
+}
+
+/// Either [`Const`] or [`Var`].
+pub trait Mutability: sealed::Sealed {}
+
+impl Mutability for Const {}
+impl Mutability for Var {}
+
+/// A list that can be reassigned if `B` is [`Var`] and changed if `C` is [`Var`].
+///
+/// Usually named through one of the aliases [`ConstConst`], [`ConstVar`], [`VarConst`] and [`VarVar`].
+pub struct Binding<T, B: Mutability, C: Mutability> {
+    list: List<T>,
+    mode: PhantomData<(B, C)>,
+}
+
+/// A list that can neither be reassigned nor changed.
+pub type ConstConst<T> = Binding<T, Const, Const>;
+/// A list that can be changed but not reassigned.
+pub type ConstVar<T> = Binding<T, Const, Var>;
+/// A list that can be reassigned but not changed.
+pub type VarConst<T> = Binding<T, Var, Const>;
+/// A list that can be reassigned and changed.
+pub type VarVar<T> = Binding<T, Var, Var>;
+
+impl<T, B: Mutability, C: Mutability> Binding<T, B, C> {
+    /// Binds a list.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstConst};
+    ///
+    /// let scores = ConstConst::new(dlist![3, 2, 5]);
+    ///
+    /// assert_eq!(scores[-1.0], 3);
+    /// ```
+    pub fn new(list: List<T>) -> Self {
+        Binding {
+            list,
+            mode: PhantomData,
+        }
+    }
+
+    /// Returns the bound list.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, VarConst};
+    ///
+    /// let scores = VarConst::new(dlist![3, 2, 5]);
+    ///
+    /// assert_eq!(scores.into_inner(), dlist![3, 2, 5]);
+    /// ```
+    pub fn into_inner(self) -> List<T> {
+        self.list
+    }
+
+    /// Returns the bound list, which is also what `Deref` gives.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstConst};
+    ///
+    /// let scores = ConstConst::new(dlist![3, 2, 5]);
+    ///
+    /// assert_eq!(scores.as_list(), &dlist![3, 2, 5]);
+    /// ```
+    pub fn as_list(&self) -> &List<T> {
+        &self.list
+    }
+
+    /// Makes the contents unchangeable.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstConst, ConstVar};
+    ///
+    /// let mut scores = ConstVar::new(dlist![3, 2]);
+    /// scores.push(5);
+    /// let scores: ConstConst<i32> = scores.freeze();
+    ///
+    /// assert_eq!(scores.len(), 3);
+    /// ```
+    pub fn freeze(self) -> Binding<T, B, Const> {
+        Binding::new(self.list)
+    }
+
+    /// Makes the binding unchangeable.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstVar, VarVar};
+    ///
+    /// let mut scores = VarVar::new(dlist![3]);
+    /// scores.reassign(dlist![3, 2]);
+    /// let mut scores: ConstVar<i32> = scores.fix();
+    /// scores.push(5);
+    ///
+    /// assert_eq!(scores.as_list(), &dlist![3, 2, 5]);
+    /// ```
+    pub fn fix(self) -> Binding<T, Const, C> {
+        Binding::new(self.list)
+    }
+}
+
+impl<T, C: Mutability> Binding<T, Var, C> {
+    /// Binds another list, returning the previous one.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, VarConst};
+    ///
+    /// let mut scores = VarConst::new(dlist![3, 2, 5]);
+    ///
+    /// assert_eq!(scores.reassign(dlist![1]), dlist![3, 2, 5]);
+    /// assert_eq!(scores[-1.0], 1);
+    /// ```
+    pub fn reassign(&mut self, list: List<T>) -> List<T> {
+        core::mem::replace(&mut self.list, list)
+    }
+}
+
+impl<T, B: Mutability> Binding<T, B, Var> {
+    /// Pushes an element, like [`List::push`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{ConstVar, List};
+    ///
+    /// let mut list = ConstVar::new(List::new());
+    /// list.push(1);
+    ///
+    /// assert_eq!(list.peek(), Some(&1));
+    /// ```
+    pub fn push(&mut self, elem: T) {
+        self.list.push(elem);
+    }
+
+    /// Pops an element, like [`List::pop`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstVar};
+    ///
+    /// let mut list = ConstVar::new(dlist![1, 2]);
+    ///
+    /// assert_eq!(list.pop(), Some(2));
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    /// Returns a mutable reference to the first element, like [`List::peek_mut`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstVar};
+    ///
+    /// let mut list = ConstVar::new(dlist![1, 2]);
+    /// if let Some(value) = list.peek_mut() {
+    ///     *value = 42;
+    /// }
+    ///
+    /// assert_eq!(list.peek(), Some(&42));
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.peek_mut()
+    }
+
+    /// Returns a mutable iterator, like [`List::iter_mut`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstVar};
+    ///
+    /// let mut list = ConstVar::new(dlist![1, 2]);
+    /// for value in list.iter_mut() {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// assert_eq!(list.as_list(), &dlist![10, 20]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.list.iter_mut()
+    }
+
+    /// Returns a mutable reference to the element at an index, like [`List::get_mut`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstVar};
+    ///
+    /// let mut list = ConstVar::new(dlist![1, 2]);
+    ///
+    /// assert_eq!(list.get_mut(0.0), Some(&mut 2));
+    /// assert_eq!(list.get_mut(1.0), None);
+    /// ```
+    pub fn get_mut(&mut self, index: f32) -> Option<&mut T> {
+        self.list.get_mut(index)
+    }
+
+    /// Inserts an element at an index, like [`List::insert`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstVar};
+    ///
+    /// let mut list = ConstVar::new(dlist![1, 2]);
+    /// list.insert(-0.5, 42);
+    ///
+    /// assert_eq!(list.as_list(), &dlist![1, 42, 2]);
+    /// ```
+    pub fn insert(&mut self, index: f32, elem: T) {
+        self.list.insert(index, elem);
+    }
+
+    /// Removes the element at an index, like [`List::remove`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::{dlist, ConstVar};
+    ///
+    /// let mut list = ConstVar::new(dlist![1, 2]);
+    ///
+    /// assert_eq!(list.remove(-1.0), Some(1));
+    /// assert_eq!(list.as_list(), &dlist![2]);
+    /// ```
+    pub fn remove(&mut self, index: f32) -> Option<T> {
+        self.list.remove(index)
+    }
+}
+
+impl<T, B: Mutability, C: Mutability> Deref for Binding<T, B, C> {
+    type Target = List<T>;
+
+    fn deref(&self) -> &List<T> {
+        &self.list
+    }
+}
+
+impl<T, B: Mutability, C: Mutability> From<List<T>> for Binding<T, B, C> {
+    fn from(list: List<T>) -> Self {
+        Binding::new(list)
+    }
+}
+
+impl<T, B: Mutability, C: Mutability> From<Binding<T, B, C>> for List<T> {
+    fn from(binding: Binding<T, B, C>) -> Self {
+        binding.list
+    }
+}
+
+impl<T, B: Mutability, C: Mutability> FromIterator<T> for Binding<T, B, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Binding::new(iter.into_iter().collect())
+    }
+}
+
+impl<T, B: Mutability> Extend<T> for Binding<T, B, Var> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+impl<T: Clone, B: Mutability, C: Mutability> Clone for Binding<T, B, C> {
+    fn clone(&self) -> Self {
+        Binding::new(self.list.clone())
+    }
+}
+
+impl<T, B: Mutability, C: Mutability> Default for Binding<T, B, C> {
+    fn default() -> Self {
+        Binding::new(List::new())
+    }
+}
+
+impl<T: PartialEq, B: Mutability, C: Mutability> PartialEq for Binding<T, B, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<T: Eq, B: Mutability, C: Mutability> Eq for Binding<T, B, C> {}
+
+impl<T: Hash, B: Mutability, C: Mutability> Hash for Binding<T, B, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.list.hash(state);
+    }
+}
+
+impl<T: fmt::Debug, B: Mutability, C: Mutability> fmt::Debug for Binding<T, B, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.list, f)
+    }
+}
+
+impl<T: fmt::Display, B: Mutability, C: Mutability> fmt::Display for Binding<T, B, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.list, f)
+    }
+}
+
+impl<T, B: Mutability, C: Mutability> Index<f32> for Binding<T, B, C> {
+    type Output = T;
+
+    fn index(&self, index: f32) -> &T {
+        &self.list[index]
+    }
+}
+
+impl<T, B: Mutability> IndexMut<f32> for Binding<T, B, Var> {
+    fn index_mut(&mut self, index: f32) -> &mut T {
+        &mut self.list[index]
+    }
+}
+
+impl<T, B: Mutability, C: Mutability> IntoIterator for Binding<T, B, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T, B: Mutability, C: Mutability> IntoIterator for &'a Binding<T, B, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.list.iter()
+    }
+}
+
+impl<'a, T, B: Mutability> IntoIterator for &'a mut Binding<T, B, Var> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.list.iter_mut()
+    }
+}
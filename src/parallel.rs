@@ -0,0 +1,372 @@
+//! Parallel iteration and sorting for `List`, enabled by the `parallel` feature.
+//!
+//! A linked list cannot be split without walking it, so the elements are collected once,
+//! split into one chunk per thread, and handed to [`std::thread::scope`].
+//! Results come back in the same order as [`List::iter`], starting at the highest index.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::dlist;
+//!
+//! let mut list = dlist![3, 1, 2];
+//! assert_eq!(list.par_iter().map(|x| x * 10), vec![20, 10, 30]);
+//!
+//! list.par_iter_mut().for_each(|x| *x += 1);
+//! list.par_sort();
+//! assert_eq!(list, dlist![2, 3, 4]);
+//! ```
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{self, AtomicBool};
+use std::thread::{self, ScopedJoinHandle};
+
+use crate::List;
+
+/// A parallel iterator over references to the elements of a [`List`], returned by [`List::par_iter`].
+pub struct ParIter<'a, T> {
+    elems: Vec<&'a T>,
+    threads: usize,
+}
+
+/// A parallel iterator over mutable references to the elements of a [`List`], returned by [`List::par_iter_mut`].
+pub struct ParIterMut<'a, T> {
+    elems: Vec<&'a mut T>,
+    threads: usize,
+}
+
+fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Lists up to this length are sorted on the calling thread, where spawning would cost more than it saves.
+const SEQUENTIAL_SORT_LEN: usize = 4096;
+
+fn chunk_size(len: usize, threads: usize) -> usize {
+    len.div_ceil(threads).max(1)
+}
+
+/// Joins a thread, passing on its panic.
+fn join<R>(handle: ScopedJoinHandle<'_, R>) -> R {
+    handle
+        .join()
+        .unwrap_or_else(|err| panic::resume_unwind(err))
+}
+
+/// Joins the threads in order, passing on the first panic.
+fn join_all<R>(handles: Vec<ScopedJoinHandle<'_, Vec<R>>>) -> Vec<R> {
+    handles.into_iter().flat_map(join).collect()
+}
+
+/// A run of elements, with the panic of the comparison that stopped sorting it, if any.
+type Run<T> = (Vec<T>, Option<Box<dyn Any + Send>>);
+
+/// Sorts a run, handing its elements back along with the panic if `compare` panics.
+fn sort<T, F>(mut run: Vec<T>, compare: &F) -> Run<T>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    // `sort_by` keeps every element in the vector when the comparison panics.
+    let panic = panic::catch_unwind(AssertUnwindSafe(|| run.sort_by(compare))).err();
+    (run, panic)
+}
+
+/// Merges two sorted runs into one, taking from `left` first on ties to keep the sort stable.
+///
+/// The order is worked out on borrowed elements before anything moves,
+/// so if `compare` panics, both runs are handed back whole along with the panic.
+fn merge<T, F>(mut left: Vec<T>, mut right: Vec<T>, compare: &F) -> Run<T>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    let order = panic::catch_unwind(AssertUnwindSafe(|| {
+        let (mut l, mut r) = (0, 0);
+        let mut takes_right = Vec::with_capacity(left.len() + right.len());
+        while l < left.len() && r < right.len() {
+            let right_first = compare(&right[r], &left[l]) == Ordering::Less;
+            takes_right.push(right_first);
+            if right_first {
+                r += 1;
+            } else {
+                l += 1;
+            }
+        }
+        takes_right
+    }));
+    let order = match order {
+        Ok(order) => order,
+        Err(panic) => {
+            left.append(&mut right);
+            return (left, Some(panic));
+        }
+    };
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter();
+    let mut right = right.into_iter();
+    for takes_right in order {
+        merged.extend(if takes_right {
+            right.next()
+        } else {
+            left.next()
+        });
+    }
+    merged.extend(left);
+    merged.extend(right);
+    (merged, None)
+}
+
+/// Separates the runs from the first panic among them.
+fn settle<T>(results: Vec<Run<T>>) -> (Vec<Vec<T>>, Option<Box<dyn Any + Send>>) {
+    let mut first = None;
+    let runs = results
+        .into_iter()
+        .map(|(run, panic)| {
+            if first.is_none() {
+                first = panic;
+            }
+            run
+        })
+        .collect();
+    (runs, first)
+}
+
+impl<T: Sync> List<T> {
+    /// Returns a parallel iterator over the list, in the order of [`List::iter`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    ///
+    /// let list = dlist![1, 2, 3, 4];
+    ///
+    /// assert!(list.par_iter().any(|&x| x == 3));
+    /// assert_eq!(list.par_iter().with_threads(2).map(|x| x * x), vec![16, 9, 4, 1]);
+    /// ```
+    pub fn par_iter(&self) -> ParIter<'_, T> {
+        ParIter {
+            elems: self.iter().collect(),
+            threads: default_threads(),
+        }
+    }
+}
+
+impl<T: Send> List<T> {
+    /// Returns a parallel iterator that allows modifying each element.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    ///
+    /// let mut list = dlist![1, 2, 3];
+    /// list.par_iter_mut().for_each(|x| *x *= 2);
+    ///
+    /// assert_eq!(list, dlist![2, 4, 6]);
+    /// ```
+    pub fn par_iter_mut(&mut self) -> ParIterMut<'_, T> {
+        ParIterMut {
+            elems: self.iter_mut().collect(),
+            threads: default_threads(),
+        }
+    }
+
+    /// Sorts the list with a comparator, from index -1 up, using several threads.
+    ///
+    /// The sort is stable. Short lists are sorted on the calling thread;
+    /// longer ones are split into one run per thread, and the sorted runs are merged pairwise in parallel.
+    ///
+    /// # Panics
+    /// If `compare` panics, the panic is passed on once every element is back in the list,
+    /// in an unspecified order.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    ///
+    /// let mut list = dlist!["ccc", "a", "bb"];
+    /// list.par_sort_by(|a, b| b.len().cmp(&a.len()));
+    ///
+    /// assert_eq!(list, dlist!["ccc", "bb", "a"]);
+    /// ```
+    pub fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let elems = Vec::from(std::mem::take(self));
+        let threads = default_threads();
+        let (sorted, panic) = if elems.len() <= SEQUENTIAL_SORT_LEN || threads == 1 {
+            sort(elems, &compare)
+        } else {
+            sort_runs(elems, threads, &compare)
+        };
+        // Every element is back in the list before a panic from `compare` is passed on.
+        *self = List::from(sorted);
+        if let Some(panic) = panic {
+            panic::resume_unwind(panic);
+        }
+    }
+}
+
+/// Splits `elems` into one run per thread, sorts the runs in parallel and merges them pairwise.
+fn sort_runs<T, F>(elems: Vec<T>, threads: usize, compare: &F) -> Run<T>
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let size = chunk_size(elems.len(), threads);
+    let mut runs = Vec::with_capacity(threads);
+    let mut rest = elems.into_iter();
+    while rest.len() > 0 {
+        runs.push(rest.by_ref().take(size).collect::<Vec<_>>());
+    }
+    thread::scope(|scope| {
+        let handles: Vec<_> = runs
+            .into_iter()
+            .map(|run| scope.spawn(move || sort(run, compare)))
+            .collect();
+        let (mut runs, mut panic) = settle(handles.into_iter().map(join).collect());
+        // Merge neighbouring runs pairwise, halving their number every round.
+        while runs.len() > 1 && panic.is_none() {
+            let mut pairs = runs.into_iter();
+            let mut handles = Vec::new();
+            while let Some(left) = pairs.next() {
+                let right = pairs.next();
+                handles.push(scope.spawn(move || match right {
+                    Some(right) => merge(left, right, compare),
+                    None => (left, None),
+                }));
+            }
+            (runs, panic) = settle(handles.into_iter().map(join).collect());
+        }
+        (runs.into_iter().flatten().collect(), panic)
+    })
+}
+
+impl<T: Ord + Send> List<T> {
+    /// Sorts the list from index -1 up, using several threads.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    ///
+    /// let mut list = dlist![3, 1, 2];
+    /// list.par_sort();
+    ///
+    /// assert_eq!(list, dlist![1, 2, 3]);
+    /// ```
+    pub fn par_sort(&mut self) {
+        self.par_sort_by(T::cmp);
+    }
+}
+
+impl<'a, T: Sync> ParIter<'a, T> {
+    /// Sets how many threads to use, at least one. Defaults to the available parallelism.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Calls `f` on every element.
+    pub fn for_each<F>(self, f: F)
+    where
+        F: Fn(&T) + Sync,
+    {
+        self.map(f);
+    }
+
+    /// Calls `f` on every element, returning the results in the order of [`List::iter`].
+    pub fn map<R, F>(self, f: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        let size = chunk_size(self.elems.len(), self.threads);
+        let f = &f;
+        thread::scope(|scope| {
+            let handles = self
+                .elems
+                .chunks(size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(|elem| f(elem)).collect()))
+                .collect();
+            join_all(handles)
+        })
+    }
+
+    /// Returns `true` if `f` returns `true` for any element.
+    ///
+    /// Every thread stops as soon as one of them finds such an element.
+    pub fn any<F>(self, f: F) -> bool
+    where
+        F: Fn(&T) -> bool + Sync,
+    {
+        let size = chunk_size(self.elems.len(), self.threads);
+        let found = AtomicBool::new(false);
+        let (f, found_ref) = (&f, &found);
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .elems
+                .chunks(size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        for elem in chunk {
+                            if found_ref.load(atomic::Ordering::Relaxed) {
+                                return;
+                            }
+                            if f(elem) {
+                                found_ref.store(true, atomic::Ordering::Relaxed);
+                                return;
+                            }
+                        }
+                    })
+                })
+                .collect();
+            handles.into_iter().for_each(join);
+        });
+        found.into_inner()
+    }
+
+    /// Returns `true` if `f` returns `true` for every element.
+    ///
+    /// Every thread stops as soon as one of them finds an element for which `f` returns `false`.
+    pub fn all<F>(self, f: F) -> bool
+    where
+        F: Fn(&T) -> bool + Sync,
+    {
+        !self.any(|elem| !f(elem))
+    }
+}
+
+impl<'a, T: Send> ParIterMut<'a, T> {
+    /// Sets how many threads to use, at least one. Defaults to the available parallelism.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Calls `f` on every element.
+    pub fn for_each<F>(self, f: F)
+    where
+        F: Fn(&mut T) + Sync,
+    {
+        self.map(f);
+    }
+
+    /// Calls `f` on every element, returning the results in the order of [`List::iter`].
+    pub fn map<R, F>(mut self, f: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(&mut T) -> R + Sync,
+    {
+        let size = chunk_size(self.elems.len(), self.threads);
+        let f = &f;
+        thread::scope(|scope| {
+            let handles = self
+                .elems
+                .chunks_mut(size)
+                .map(|chunk| scope.spawn(move || chunk.iter_mut().map(|elem| f(elem)).collect()))
+                .collect();
+            join_all(handles)
+        })
+    }
+}
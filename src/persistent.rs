@@ -0,0 +1,496 @@
+//! Persistent lists that share structure between versions.
+//!
+//! A [`PList`] never changes. `push`, `pop`, `insert` and `remove` return a new version instead,
+//! which shares every node below the change with the old one, so keeping old versions around is cheap.
+//! Like [`List`], the element pushed last has the highest index, and indexing starts at -1.
+//!
+//! Nodes are shared through `Rc` in [`RcPList`] and through `Arc` in [`ArcPList`],
+//! which can be sent to other threads.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::persistent::RcPList;
+//!
+//! let base = RcPList::new().push(1).push(2);
+//! let edited = base.insert(-0.5, 42);
+//!
+//! assert_eq!(base.to_string(), "[1, 2]");
+//! assert_eq!(edited.to_string(), "[1, 42, 2]");
+//! assert_eq!(edited.get(-1.0), Some(&1));
+//! ```
+
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, Index};
+
+use crate::List;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::RcPointer {}
+    impl Sealed for super::ArcPointer {}
+}
+
+/// How the nodes of a [`PList`] are shared, either [`RcPointer`] or [`ArcPointer`].
+pub trait PointerKind: sealed::Sealed {
+    /// The shared pointer to a node.
+    type Pointer<U>: Deref<Target = U> + Clone;
+
+    /// Wraps a value in a new pointer.
+    fn new<U>(value: U) -> Self::Pointer<U>;
+
+    /// Returns the value if this was the last pointer to it.
+    fn into_inner<U>(pointer: Self::Pointer<U>) -> Option<U>;
+
+    /// Returns `true` if both pointers point to the same value.
+    fn ptr_eq<U>(a: &Self::Pointer<U>, b: &Self::Pointer<U>) -> bool;
+}
+
+/// Shares nodes through `Rc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RcPointer {}
+
+/// Shares nodes through `Arc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArcPointer {}
+
+impl PointerKind for RcPointer {
+    type Pointer<U> = Rc<U>;
+
+    fn new<U>(value: U) -> Rc<U> {
+        Rc::new(value)
+    }
+
+    fn into_inner<U>(pointer: Rc<U>) -> Option<U> {
+        Rc::into_inner(pointer)
+    }
+
+    fn ptr_eq<U>(a: &Rc<U>, b: &Rc<U>) -> bool {
+        Rc::ptr_eq(a, b)
+    }
+}
+
+impl PointerKind for ArcPointer {
+    type Pointer<U> = Arc<U>;
+
+    fn new<U>(value: U) -> Arc<U> {
+        Arc::new(value)
+    }
+
+    fn into_inner<U>(pointer: Arc<U>) -> Option<U> {
+        Arc::into_inner(pointer)
+    }
+
+    fn ptr_eq<U>(a: &Arc<U>, b: &Arc<U>) -> bool {
+        Arc::ptr_eq(a, b)
+    }
+}
+
+type Link<T, P> = Option<<P as PointerKind>::Pointer<Node<T, P>>>;
+
+struct Node<T, P: PointerKind> {
+    elem: T,
+    next: Link<T, P>,
+}
+
+/// An immutable list whose versions share their nodes.
+pub struct PList<T, P: PointerKind = RcPointer> {
+    head: Link<T, P>,
+    len: usize,
+}
+
+/// A [`PList`] sharing its nodes through `Rc`.
+pub type RcPList<T> = PList<T, RcPointer>;
+/// A [`PList`] sharing its nodes through `Arc`, so it can be sent to other threads.
+pub type ArcPList<T> = PList<T, ArcPointer>;
+
+/// An iterator over a [`PList`], from the highest index down, returned by [`PList::iter`].
+pub struct Iter<'a, T, P: PointerKind> {
+    next: Option<&'a Node<T, P>>,
+}
+
+impl<T, P: PointerKind> PList<T, P> {
+    /// Creates an empty list.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::ArcPList;
+    ///
+    /// let list: ArcPList<i32> = ArcPList::new();
+    ///
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        PList { head: None, len: 0 }
+    }
+
+    /// Returns a version with `elem` pushed, like [`List::push`].
+    ///
+    /// The new version shares every node with this one.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::RcPList;
+    ///
+    /// let one = RcPList::new().push(1);
+    /// let two = one.push(2);
+    ///
+    /// assert_eq!(one.len(), 1);
+    /// assert_eq!(two.peek(), Some(&2));
+    /// ```
+    pub fn push(&self, elem: T) -> Self {
+        PList {
+            head: Some(P::new(Node {
+                elem,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    /// Returns the first element and a version without it, like [`List::pop`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::RcPList;
+    ///
+    /// let list = RcPList::new().push(1).push(2);
+    /// let (first, rest) = list.pop().unwrap();
+    ///
+    /// assert_eq!(first, &2);
+    /// assert_eq!(rest.to_string(), "[1]");
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    pub fn pop(&self) -> Option<(&T, Self)> {
+        self.head.as_ref().map(|node| {
+            let rest = PList {
+                head: node.next.clone(),
+                len: self.len - 1,
+            };
+            (&node.elem, rest)
+        })
+    }
+
+    /// Returns the first element, which has the highest index.
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    /// Returns the number of elements, without walking the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the list, like [`List::iter`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::RcPList;
+    ///
+    /// let list = RcPList::new().push(1).push(2);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &1]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T, P> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    /// Returns the element at an index, like [`List::get`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::RcPList;
+    ///
+    /// let list = RcPList::new().push(1).push(2).push(3);
+    ///
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// assert_eq!(list.get(0.5), Some(&3));
+    /// assert_eq!(list.get(2.0), None);
+    /// ```
+    pub fn get(&self, index: f32) -> Option<&T> {
+        let skip = self.skip(index)?;
+        self.iter().nth(skip)
+    }
+
+    /// Returns `true` if both lists are the same version, or versions sharing their first node.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::RcPList;
+    ///
+    /// let list = RcPList::new().push(1);
+    ///
+    /// assert!(list.ptr_eq(&list.clone()));
+    /// assert!(!list.ptr_eq(&RcPList::new().push(1)));
+    /// ```
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => P::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns how many nodes come before the node at `index`, using the same rules as [`List::get`].
+    fn skip(&self, index: f32) -> Option<usize> {
+        let position = crate::position(index, self.len)?;
+        // The head holds the highest index.
+        Some(self.len - 1 - position)
+    }
+
+    /// Returns a version with `prefix`, given from the first node down, in front of `rest`.
+    fn rebuild(prefix: Vec<T>, rest: Link<T, P>, len: usize) -> Self {
+        let mut head = rest;
+        for elem in prefix.into_iter().rev() {
+            head = Some(P::new(Node { elem, next: head }));
+        }
+        PList { head, len }
+    }
+}
+
+impl<T: Clone, P: PointerKind> PList<T, P> {
+    /// Returns a version with `elem` inserted at an index, like [`List::insert`].
+    ///
+    /// Nodes below the index are shared, the ones above it are copied.
+    /// Like `List::insert`, an index out of bounds leaves the list as it is.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::RcPList;
+    ///
+    /// let list = RcPList::new().push(1).push(2);
+    ///
+    /// assert_eq!(list.insert(-0.5, 42).to_string(), "[1, 42, 2]");
+    /// assert_eq!(list.insert(5.0, 42).to_string(), "[1, 2]");
+    /// ```
+    pub fn insert(&self, index: f32, elem: T) -> Self {
+        let Some(skip) = self.skip(index) else {
+            return self.clone();
+        };
+        let mut prefix: Vec<T> = self.iter().take(skip + 1).cloned().collect();
+        let target = self.node(skip);
+        let rest = Some(P::new(Node {
+            elem,
+            next: target.next.clone(),
+        }));
+        let last = prefix.pop().expect("the prefix contains the target node");
+        let rest = Some(P::new(Node { elem: last, next: rest }));
+        PList::rebuild(prefix, rest, self.len + 1)
+    }
+
+    /// Returns the element at an index and a version without it, like [`List::remove`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::persistent::RcPList;
+    ///
+    /// let list = RcPList::new().push(1).push(2).push(3);
+    /// let (removed, rest) = list.remove(0.0).unwrap();
+    ///
+    /// assert_eq!(removed, &2);
+    /// assert_eq!(rest.to_string(), "[1, 3]");
+    /// assert!(list.remove(3.0).is_none());
+    /// ```
+    pub fn remove(&self, index: f32) -> Option<(&T, Self)> {
+        let skip = self.skip(index)?;
+        let prefix: Vec<T> = self.iter().take(skip).cloned().collect();
+        let target = self.node(skip);
+        let rest = PList::rebuild(prefix, target.next.clone(), self.len - 1);
+        Some((&target.elem, rest))
+    }
+
+    fn node(&self, skip: usize) -> &Node<T, P> {
+        let mut node = self.head.as_deref().expect("skip is in bounds");
+        for _ in 0..skip {
+            node = node.next.as_deref().expect("skip is in bounds");
+        }
+        node
+    }
+}
+
+impl<T, P: PointerKind> Drop for PList<T, P> {
+    fn drop(&mut self) {
+        // Only the nodes no other version uses are freed, one by one, so long chains do not overflow the stack.
+        let mut cur_link = self.head.take();
+        while let Some(pointer) = cur_link {
+            match P::into_inner(pointer) {
+                Some(mut node) => cur_link = node.next.take(),
+                None => break,
+            }
+        }
+    }
+}
+
+impl<T, P: PointerKind> Clone for PList<T, P> {
+    /// Returns the same version, without copying any node.
+    fn clone(&self) -> Self {
+        PList {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T, P: PointerKind> Default for PList<T, P> {
+    fn default() -> Self {
+        PList::new()
+    }
+}
+
+impl<T: PartialEq, P: PointerKind> PartialEq for PList<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<T: Eq, P: PointerKind> Eq for PList<T, P> {}
+
+/// Hashes the length and then every element from index -1 up, like a [`List`] with the same elements.
+impl<T: Hash, P: PointerKind> Hash for PList<T, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for elem in self.index_order() {
+            elem.hash(state);
+        }
+    }
+}
+
+/// Formats the elements like a slice, starting at index -1.
+impl<T: fmt::Debug, P: PointerKind> fmt::Debug for PList<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.index_order()).finish()
+    }
+}
+
+/// Formats the elements like a [`List`]: `[1, 2, 3]`, or one `index: element` line each with `{:#}`.
+impl<T: fmt::Display, P: PointerKind> fmt::Display for PList<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            for (i, elem) in self.index_order().enumerate() {
+                if i > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "{}: {}", i as isize - 1, elem)?;
+            }
+            Ok(())
+        } else {
+            write!(f, "[")?;
+            for (i, elem) in self.index_order().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", elem)?;
+            }
+            write!(f, "]")
+        }
+    }
+}
+
+impl<T, P: PointerKind> FromIterator<T> for PList<T, P> {
+    /// Pushes the elements in order, so the first one ends up at index -1.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = PList::new();
+        for elem in iter {
+            list = list.push(elem);
+        }
+        list
+    }
+}
+
+impl<T, P: PointerKind> From<List<T>> for PList<T, P> {
+    fn from(list: List<T>) -> Self {
+        Vec::from(list).into_iter().collect()
+    }
+}
+
+impl<T: Clone, P: PointerKind> From<&PList<T, P>> for List<T> {
+    fn from(list: &PList<T, P>) -> Self {
+        let mut elems: Vec<T> = list.iter().cloned().collect();
+        elems.reverse();
+        List::from(elems)
+    }
+}
+
+impl<T, P: PointerKind> Index<f32> for PList<T, P> {
+    type Output = T;
+
+    fn index(&self, index: f32) -> &T {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a, T, P: PointerKind> IntoIterator for &'a PList<T, P> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, P>;
+
+    fn into_iter(self) -> Iter<'a, T, P> {
+        self.iter()
+    }
+}
+
+impl<'a, T, P: PointerKind> Iterator for Iter<'a, T, P> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<T, P: PointerKind> PList<T, P> {
+    /// Returns an iterator over the elements starting at index -1, without allocating.
+    fn index_order(&self) -> IndexOrder<'_, T, P> {
+        let mut order = IndexOrder {
+            runs: [(None, 0); crate::RUNS],
+            depth: 0,
+        };
+        if self.len > 0 {
+            order.runs[0] = (self.head.as_deref(), self.len);
+            order.depth = 1;
+        }
+        order
+    }
+}
+
+/// Walks the nodes back to front the way `List`'s index order does:
+/// the later half of a run of nodes is visited before its first half, one walk per halving.
+struct IndexOrder<'a, T, P: PointerKind> {
+    /// Runs of nodes still to visit, the last one first: where each starts and how many nodes it spans.
+    runs: [(Option<&'a Node<T, P>>, usize); crate::RUNS],
+    depth: usize,
+}
+
+impl<'a, T, P: PointerKind> Iterator for IndexOrder<'a, T, P> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            if self.depth == 0 {
+                return None;
+            }
+            self.depth -= 1;
+            let (first, count) = self.runs[self.depth];
+            let first = first?;
+            if count == 1 {
+                return Some(&first.elem);
+            }
+            let half = count / 2;
+            let later = core::iter::successors(Some(first), |node| node.next.as_deref()).nth(half);
+            self.runs[self.depth] = (Some(first), half);
+            self.runs[self.depth + 1] = (later, count - half);
+            self.depth += 2;
+        }
+    }
+}
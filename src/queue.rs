@@ -0,0 +1,450 @@
+//! A blocking work queue for many producers and consumers, backed by a `List`.
+//!
+//! [`ListQueue`] keeps its elements in `List`s behind a `Mutex`, with the `Condvar` logic around it:
+//! `recv` waits for an element, `send` waits for room if the queue has a limit,
+//! and `close` wakes everyone up so the consumers can drain what is left and stop.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//! use std::thread;
+//! use dreamberd_array::queue::ListQueue;
+//!
+//! let queue = Arc::new(ListQueue::with_limit(2));
+//! let consumer = {
+//!     let queue = Arc::clone(&queue);
+//!     thread::spawn(move || {
+//!         let mut done = Vec::new();
+//!         while let Ok(job) = queue.recv() {
+//!             done.push(job);
+//!         }
+//!         done
+//!     })
+//! };
+//! for job in 1..=5 {
+//!     queue.send(job).unwrap();
+//! }
+//! queue.close();
+//!
+//! assert_eq!(consumer.join().unwrap(), vec![1, 2, 3, 4, 5]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::List;
+
+/// Which element a [`ListQueue`] hands out first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// The oldest element, like a queue.
+    #[default]
+    Fifo,
+    /// The newest element, like a stack.
+    Lifo,
+}
+
+/// The error returned by [`ListQueue::send`] once the queue is closed, holding the element that was not sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sending on a closed queue")
+    }
+}
+
+impl<T: fmt::Debug> Error for SendError<T> {}
+
+/// The ways [`ListQueue::try_send`] can fail, holding the element that was not sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrySendError<T> {
+    /// The queue is at its limit.
+    Full(T),
+    /// The queue is closed.
+    Closed(T),
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "sending on a full queue"),
+            TrySendError::Closed(_) => write!(f, "sending on a closed queue"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for TrySendError<T> {}
+
+/// The error returned by [`ListQueue::recv`] once the queue is closed and empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiving on a closed queue")
+    }
+}
+
+impl Error for RecvError {}
+
+/// The ways [`ListQueue::try_recv`] can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The queue is empty, but still open.
+    Empty,
+    /// The queue is closed and empty.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "receiving on an empty queue"),
+            TryRecvError::Closed => write!(f, "receiving on a closed queue"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+/// The ways [`ListQueue::recv_timeout`] can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    /// Nothing arrived in time.
+    Timeout,
+    /// The queue is closed and empty.
+    Closed,
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => write!(f, "timed out waiting on a queue"),
+            RecvTimeoutError::Closed => write!(f, "receiving on a closed queue"),
+        }
+    }
+}
+
+impl Error for RecvTimeoutError {}
+
+struct State<T> {
+    /// Newly sent elements with [`Order::Fifo`], the newest at the highest index.
+    inbox: List<T>,
+    /// Elements ready to be received, the next one at the highest index.
+    /// With [`Order::Lifo`] every element is sent straight here.
+    outbox: List<T>,
+    closed: bool,
+}
+
+impl<T> State<T> {
+    fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inbox.is_empty() && self.outbox.is_empty()
+    }
+
+    fn take(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            // Popping the inbox empty pushes its oldest element last, to the highest index.
+            while let Some(elem) = self.inbox.pop() {
+                self.outbox.push(elem);
+            }
+        }
+        self.outbox.pop()
+    }
+
+    /// Returns the waiting elements from index -1 up, as [`ListQueue::into_list`] would lay them out.
+    fn elems(&self) -> impl Iterator<Item = &T> {
+        self.inbox.iter().chain(self.outbox.index_order())
+    }
+}
+
+/// A blocking queue that any number of threads can send to and receive from.
+pub struct ListQueue<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    limit: Option<usize>,
+    order: Order,
+}
+
+impl<T> ListQueue<T> {
+    /// Creates an empty, unbounded, first-in first-out queue.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::queue::{ListQueue, Order};
+    ///
+    /// let queue: ListQueue<i32> = ListQueue::new();
+    ///
+    /// assert_eq!(queue.limit(), None);
+    /// assert_eq!(queue.order(), Order::Fifo);
+    /// ```
+    pub fn new() -> Self {
+        ListQueue {
+            state: Mutex::new(State {
+                inbox: List::new(),
+                outbox: List::new(),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            limit: None,
+            order: Order::Fifo,
+        }
+    }
+
+    /// Creates an empty queue that holds at most `limit` elements.
+    ///
+    /// # Panics
+    /// Panics if `limit` is `0`.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::queue::{ListQueue, TrySendError};
+    ///
+    /// let queue = ListQueue::with_limit(1);
+    /// queue.try_send(1).unwrap();
+    ///
+    /// assert_eq!(queue.try_send(2), Err(TrySendError::Full(2)));
+    /// ```
+    pub fn with_limit(limit: usize) -> Self {
+        assert!(limit > 0, "queue limit must be at least 1");
+        ListQueue {
+            limit: Some(limit),
+            ..ListQueue::new()
+        }
+    }
+
+    /// Sets which element is received first.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::queue::{ListQueue, Order};
+    ///
+    /// let queue = ListQueue::new().with_order(Order::Lifo);
+    /// queue.send(1).unwrap();
+    /// queue.send(2).unwrap();
+    ///
+    /// assert_eq!(queue.try_recv(), Ok(2));
+    /// ```
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns how many elements the queue holds at most, or `None` if there is no limit.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Returns which element is received first.
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    // Nothing panics while the lock is held, so a poisoned lock is still usable.
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_full(&self, state: &State<T>) -> bool {
+        self.limit.is_some_and(|limit| state.len() >= limit)
+    }
+
+    fn enqueue(&self, mut state: MutexGuard<'_, State<T>>, elem: T) {
+        match self.order {
+            Order::Fifo => state.inbox.push(elem),
+            Order::Lifo => state.outbox.push(elem),
+        }
+        drop(state);
+        self.not_empty.notify_one();
+    }
+
+    fn dequeue(&self, mut state: MutexGuard<'_, State<T>>) -> Option<T> {
+        let elem = state.take()?;
+        drop(state);
+        self.not_full.notify_one();
+        Some(elem)
+    }
+
+    /// Sends an element, waiting while the queue is full.
+    ///
+    /// Fails with the element if the queue is closed, also while waiting.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::queue::{ListQueue, SendError};
+    ///
+    /// let queue = ListQueue::new();
+    /// queue.send(1).unwrap();
+    /// queue.close();
+    ///
+    /// assert_eq!(queue.send(2), Err(SendError(2)));
+    /// ```
+    pub fn send(&self, elem: T) -> Result<(), SendError<T>> {
+        let state = self
+            .not_full
+            .wait_while(self.lock(), |state| !state.closed && self.is_full(state))
+            .unwrap_or_else(PoisonError::into_inner);
+        if state.closed {
+            return Err(SendError(elem));
+        }
+        self.enqueue(state, elem);
+        Ok(())
+    }
+
+    /// Sends an element if there is room right now.
+    pub fn try_send(&self, elem: T) -> Result<(), TrySendError<T>> {
+        let state = self.lock();
+        if state.closed {
+            Err(TrySendError::Closed(elem))
+        } else if self.is_full(&state) {
+            Err(TrySendError::Full(elem))
+        } else {
+            self.enqueue(state, elem);
+            Ok(())
+        }
+    }
+
+    /// Receives an element, waiting while the queue is empty.
+    ///
+    /// Elements sent before the queue was closed are still received,
+    /// after that it fails.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::queue::{ListQueue, RecvError};
+    ///
+    /// let queue = ListQueue::new();
+    /// queue.send(1).unwrap();
+    /// queue.close();
+    ///
+    /// assert_eq!(queue.recv(), Ok(1));
+    /// assert_eq!(queue.recv(), Err(RecvError));
+    /// ```
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let state = self
+            .not_empty
+            .wait_while(self.lock(), |state| !state.closed && state.is_empty())
+            .unwrap_or_else(PoisonError::into_inner);
+        self.dequeue(state).ok_or(RecvError)
+    }
+
+    /// Receives an element, waiting at most `timeout` while the queue is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use dreamberd_array::queue::{ListQueue, RecvTimeoutError};
+    ///
+    /// let queue: ListQueue<i32> = ListQueue::new();
+    ///
+    /// assert_eq!(queue.recv_timeout(Duration::from_millis(1)), Err(RecvTimeoutError::Timeout));
+    /// queue.close();
+    /// assert_eq!(queue.recv_timeout(Duration::from_secs(60)), Err(RecvTimeoutError::Closed));
+    /// ```
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut state = self.lock();
+        while !state.closed && state.is_empty() {
+            state = match deadline {
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    if left.is_zero() {
+                        return Err(RecvTimeoutError::Timeout);
+                    }
+                    self.not_empty
+                        .wait_timeout(state, left)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                // Too far in the future to represent, so wait as long as it takes.
+                None => self
+                    .not_empty
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+        self.dequeue(state).ok_or(RecvTimeoutError::Closed)
+    }
+
+    /// Receives an element if there is one right now.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let state = self.lock();
+        let closed = state.closed;
+        self.dequeue(state).ok_or(if closed {
+            TryRecvError::Closed
+        } else {
+            TryRecvError::Empty
+        })
+    }
+
+    /// Closes the queue, waking every waiting thread.
+    ///
+    /// Sending fails from now on, and receiving fails once the remaining elements are taken.
+    /// Closing twice does nothing.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// Returns `true` if the queue was closed.
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Returns the number of elements waiting in the queue.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no elements are waiting.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the elements still waiting, the next one to be received at the highest index.
+    pub fn into_list(self) -> List<T> {
+        let mut state = self.state.into_inner().unwrap_or_else(PoisonError::into_inner);
+        let mut list = List::new();
+        while let Some(elem) = state.inbox.pop() {
+            list.push(elem);
+        }
+        list.extend(Vec::from(state.outbox));
+        list
+    }
+}
+
+impl<T> Default for ListQueue<T> {
+    fn default() -> Self {
+        ListQueue::new()
+    }
+}
+
+/// Formats the waiting elements like the list [`ListQueue::into_list`] returns.
+struct Waiting<'a, T>(&'a State<T>);
+
+impl<T: fmt::Debug> fmt::Debug for Waiting<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.elems()).finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ListQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("ListQueue")
+            .field("list", &Waiting(&state))
+            .field("closed", &state.closed)
+            .field("limit", &self.limit)
+            .field("order", &self.order)
+            .finish()
+    }
+}
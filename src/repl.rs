@@ -0,0 +1,294 @@
+//! The command interpreter behind the `dreamberd-array` binary.
+//!
+//! A [`Session`] keeps named `List<Value>`s and runs one command per line.
+//! Every command that touches a list answers with the list's indexed layout afterwards.
+//!
+//! | Command                      | Effect                                              |
+//! |------------------------------|-----------------------------------------------------|
+//! | `new NAME [LITERAL]`         | Creates a list, empty or from an array literal      |
+//! | `push NAME VALUE`            | Pushes to the front, creating the list if needed    |
+//! | `pop NAME`                   | Pops from the front                                 |
+//! | `insert NAME INDEX VALUE`    | Inserts at a fractional index                       |
+//! | `set NAME INDEX VALUE`       | Overwrites the element at an index                  |
+//! | `get NAME INDEX`             | Prints the element at an index                      |
+//! | `remove NAME INDEX`          | Removes the element at an index                     |
+//! | `show [NAME]`                | Prints one list, or all of them                     |
+//! | `drop NAME`                  | Forgets a list                                      |
+//! | `help`                       | Prints this table                                   |
+//!
+//! Values use the DreamBerd literal syntax from [`crate::syntax`], for example `42`, `'text'` or `[1, 2]`.
+//! Empty lines and lines starting with `#` are ignored.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::repl::Session;
+//!
+//! let mut session = Session::new();
+//! session.execute("push xs 1").unwrap();
+//! session.execute("push xs 2").unwrap();
+//!
+//! assert_eq!(session.execute("insert xs -0.5 42").unwrap(), "xs = [1, 42, 2]\n  -1: 1\n  0: 42\n  1: 2");
+//! assert_eq!(session.execute("get xs 0").unwrap(), "xs[0] = 42\nxs = [1, 42, 2]\n  -1: 1\n  0: 42\n  1: 2");
+//! assert!(session.execute("get ys 0").is_err());
+//! ```
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::syntax::{self, ParseError};
+use crate::{List, Value};
+
+const HELP: &str = "\
+new NAME [LITERAL]        create a list, empty or from an array literal
+push NAME VALUE           push to the front, creating the list if needed
+pop NAME                  pop from the front
+insert NAME INDEX VALUE   insert at a fractional index
+set NAME INDEX VALUE      overwrite the element at an index
+get NAME INDEX            print the element at an index
+remove NAME INDEX         remove the element at an index
+show [NAME]               print one list, or all of them
+drop NAME                 forget a list
+help                      print this help";
+
+/// The ways a command can fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplError {
+    /// The first word is not a known command.
+    UnknownCommand(String),
+    /// A required argument is missing.
+    MissingArgument(&'static str),
+    /// An index that is not a number.
+    InvalidIndex(String),
+    /// No list with this name exists.
+    UnknownList(String),
+    /// The index does not point into the list.
+    OutOfBounds(String, f32),
+    /// A value or literal could not be parsed.
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReplError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplError::UnknownCommand(command) => {
+                write!(f, "unknown command {:?}, try `help`", command)
+            }
+            ReplError::MissingArgument(argument) => write!(f, "missing argument {}", argument),
+            ReplError::InvalidIndex(index) => write!(f, "invalid index {:?}", index),
+            ReplError::UnknownList(name) => write!(f, "no list named {:?}", name),
+            ReplError::OutOfBounds(name, index) => {
+                write!(f, "index {} is out of bounds for {}", index, name)
+            }
+            ReplError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ReplError {}
+
+impl From<ParseError> for ReplError {
+    fn from(err: ParseError) -> Self {
+        ReplError::Parse(err)
+    }
+}
+
+/// A set of named lists and the commands to change them.
+#[derive(Debug, Default)]
+pub struct Session {
+    lists: BTreeMap<String, List<Value>>,
+}
+
+impl Session {
+    /// Creates a session without any lists.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::repl::Session;
+    ///
+    /// let session = Session::new();
+    /// assert!(session.list("xs").is_none());
+    /// ```
+    pub fn new() -> Self {
+        Session {
+            lists: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the list with the given name.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::repl::Session;
+    /// use dreamberd_array::Value;
+    ///
+    /// let mut session = Session::new();
+    /// session.execute("new xs [1, 2]").unwrap();
+    ///
+    /// assert_eq!(session.list("xs").unwrap()[-1.0], Value::from(1));
+    /// ```
+    pub fn list(&self, name: &str) -> Option<&List<Value>> {
+        self.lists.get(name)
+    }
+
+    /// Runs a single command and returns what it prints.
+    ///
+    /// Empty lines and comments print nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::repl::Session;
+    ///
+    /// let mut session = Session::new();
+    ///
+    /// assert_eq!(session.execute("new xs ['a']").unwrap(), "xs = [\"a\"]\n  -1: \"a\"");
+    /// assert_eq!(session.execute("# a comment").unwrap(), "");
+    /// ```
+    pub fn execute(&mut self, line: &str) -> Result<String, ReplError> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(String::new());
+        }
+        let (command, rest) = split_word(line);
+        match command {
+            "help" => Ok(HELP.to_string()),
+            "new" => {
+                let (name, rest) = name(rest)?;
+                let list = if rest.is_empty() {
+                    List::new()
+                } else {
+                    syntax::parse(rest)?
+                };
+                self.lists.insert(name.to_string(), list);
+                Ok(self.layout(name))
+            }
+            "push" => {
+                let (name, rest) = name(rest)?;
+                let value = value(rest)?;
+                self.lists.entry(name.to_string()).or_default().push(value);
+                Ok(self.layout(name))
+            }
+            "pop" => {
+                let (name, _) = name(rest)?;
+                let popped = self.list_mut(name)?.pop();
+                Ok(self.with_value(name, "popped", popped))
+            }
+            "insert" => {
+                let (name, rest) = name(rest)?;
+                let (index, rest) = index(rest)?;
+                let value = value(rest)?;
+                let list = self.list_mut(name)?;
+                if list.get(index).is_none() {
+                    return Err(ReplError::OutOfBounds(name.to_string(), index));
+                }
+                list.insert(index, value);
+                Ok(self.layout(name))
+            }
+            "set" => {
+                let (name, rest) = name(rest)?;
+                let (index, rest) = index(rest)?;
+                let value = value(rest)?;
+                match self.list_mut(name)?.get_mut(index) {
+                    Some(slot) => *slot = value,
+                    None => return Err(ReplError::OutOfBounds(name.to_string(), index)),
+                }
+                Ok(self.layout(name))
+            }
+            "get" => {
+                let (name, rest) = name(rest)?;
+                let (index, _) = index(rest)?;
+                let value = self.list(name).ok_or_else(|| ReplError::UnknownList(name.to_string()))?;
+                match value.get(index) {
+                    Some(value) => Ok(format!("{}[{}] = {}\n{}", name, index, value, self.layout(name))),
+                    None => Err(ReplError::OutOfBounds(name.to_string(), index)),
+                }
+            }
+            "remove" => {
+                let (name, rest) = name(rest)?;
+                let (index, _) = index(rest)?;
+                match self.list_mut(name)?.remove(index) {
+                    Some(value) => Ok(self.with_value(name, "removed", Some(value))),
+                    None => Err(ReplError::OutOfBounds(name.to_string(), index)),
+                }
+            }
+            "show" => {
+                if rest.is_empty() {
+                    let names: Vec<&String> = self.lists.keys().collect();
+                    Ok(names
+                        .into_iter()
+                        .map(|name| self.layout(name))
+                        .collect::<Vec<String>>()
+                        .join("\n"))
+                } else {
+                    let (name, _) = name(rest)?;
+                    match self.list(name) {
+                        Some(_) => Ok(self.layout(name)),
+                        None => Err(ReplError::UnknownList(name.to_string())),
+                    }
+                }
+            }
+            "drop" => {
+                let (name, _) = name(rest)?;
+                match self.lists.remove(name) {
+                    Some(_) => Ok(format!("dropped {}", name)),
+                    None => Err(ReplError::UnknownList(name.to_string())),
+                }
+            }
+            other => Err(ReplError::UnknownCommand(other.to_string())),
+        }
+    }
+
+    fn list_mut(&mut self, name: &str) -> Result<&mut List<Value>, ReplError> {
+        self.lists
+            .get_mut(name)
+            .ok_or_else(|| ReplError::UnknownList(name.to_string()))
+    }
+
+    fn with_value(&self, name: &str, label: &str, value: Option<Value>) -> String {
+        let value = value.unwrap_or(Value::Undefined);
+        format!("{} {}\n{}", label, value, self.layout(name))
+    }
+
+    /// Formats a list as `name = [...]` followed by one indented line per element.
+    fn layout(&self, name: &str) -> String {
+        let list = &self.lists[name];
+        let mut out = format!("{} = {}", name, list);
+        for line in format!("{:#}", list).lines() {
+            out.push_str("\n  ");
+            out.push_str(line);
+        }
+        out
+    }
+}
+
+fn split_word(src: &str) -> (&str, &str) {
+    let src = src.trim_start();
+    match src.find(char::is_whitespace) {
+        Some(end) => (&src[..end], src[end..].trim()),
+        None => (src, ""),
+    }
+}
+
+fn name(src: &str) -> Result<(&str, &str), ReplError> {
+    match split_word(src) {
+        ("", _) => Err(ReplError::MissingArgument("NAME")),
+        found => Ok(found),
+    }
+}
+
+fn index(src: &str) -> Result<(f32, &str), ReplError> {
+    match split_word(src) {
+        ("", _) => Err(ReplError::MissingArgument("INDEX")),
+        (word, rest) => match word.parse::<f32>() {
+            Ok(index) if index.is_finite() => Ok((index, rest)),
+            _ => Err(ReplError::InvalidIndex(word.to_string())),
+        },
+    }
+}
+
+fn value(src: &str) -> Result<Value, ReplError> {
+    if src.is_empty() {
+        return Err(ReplError::MissingArgument("VALUE"));
+    }
+    Ok(syntax::parse_value(src)?)
+}
@@ -0,0 +1,191 @@
+//! `serde` support for `List`, enabled by the `serde` feature.
+//!
+//! By default a `List` is serialized as a plain sequence, starting at index -1.
+//! The [`indexed`] module offers a map representation keyed by the DreamBerd index instead.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::List;
+
+/// Serializes the list as a sequence, starting at index -1.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::dlist;
+///
+/// let list = dlist![1, 2, 3];
+/// assert_eq!(serde_json::to_string(&list).unwrap(), "[1,2,3]");
+/// ```
+impl<T: Serialize> Serialize for List<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let elems = self.index_order();
+        let mut seq = serializer.serialize_seq(Some(elems.len()))?;
+        for elem in elems {
+            seq.serialize_element(elem)?;
+        }
+        seq.end()
+    }
+}
+
+/// Deserializes a sequence, the first element ending up at index -1.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::List;
+///
+/// let list: List<usize> = serde_json::from_str("[1,2,3]").unwrap();
+/// assert_eq!(list[-1.0], 1);
+/// assert_eq!(list[1.0], 3);
+/// ```
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for List<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(SeqVisitor(PhantomData))
+    }
+}
+
+struct SeqVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SeqVisitor<T> {
+    type Value = List<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = List::new();
+        while let Some(elem) = seq.next_element()? {
+            list.push(elem);
+        }
+        Ok(list)
+    }
+}
+
+/// Serializes a `List` as a map from DreamBerd index to element.
+///
+/// Use it with `#[serde(with = "dreamberd_array::serde::indexed")]`.
+///
+/// Keys are written as strings, `"-1"` for the last element and counting up from there.
+/// When deserializing, keys may be strings or numbers and may be fractional.
+/// The elements are ordered by their key, so `"0.5"` lands between `"0"` and `"1"`,
+/// and the resulting list is indexed from -1 again.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::{dlist, List};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Scores {
+///     #[serde(with = "dreamberd_array::serde::indexed")]
+///     scores: List<usize>,
+/// }
+///
+/// let scores = Scores { scores: dlist![3, 2, 5] };
+/// let json = serde_json::to_string(&scores).unwrap();
+/// assert_eq!(json, r#"{"scores":{"-1":3,"0":2,"1":5}}"#);
+///
+/// let scores: Scores = serde_json::from_str(r#"{"scores":{"-1":3,"0.5":42,"0":2}}"#).unwrap();
+/// assert_eq!(scores.scores, dlist![3, 2, 42]);
+/// ```
+pub mod indexed {
+    use super::*;
+
+    /// Serializes the list as a map from DreamBerd index to element.
+    pub fn serialize<T, S>(list: &List<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        let elems = list.index_order();
+        let mut map = serializer.serialize_map(Some(elems.len()))?;
+        for (i, elem) in elems.enumerate() {
+            map.serialize_entry(&(i as isize - 1).to_string(), elem)?;
+        }
+        map.end()
+    }
+
+    /// Deserializes a map from DreamBerd index to element, ordering the elements by index.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<List<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+
+    struct MapVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for MapVisitor<T> {
+        type Value = List<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map from DreamBerd index to element")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut entries: Vec<(f32, T)> = Vec::new();
+            while let Some((Key(index), elem)) = map.next_entry()? {
+                entries.push((index, elem));
+            }
+            entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+            if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+                return Err(de::Error::custom(format_args!("duplicate index {}", pair[0].0)));
+            }
+            Ok(entries.into_iter().map(|(_, elem)| elem).collect())
+        }
+    }
+
+    struct Key(f32);
+
+    impl<'de> Deserialize<'de> for Key {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(KeyVisitor)
+        }
+    }
+
+    struct KeyVisitor;
+
+    impl KeyVisitor {
+        fn check<E: de::Error>(index: f32) -> Result<Key, E> {
+            if index.is_finite() {
+                Ok(Key(index))
+            } else {
+                Err(E::custom(format_args!("invalid index {}", index)))
+            }
+        }
+    }
+
+    impl<'de> Visitor<'de> for KeyVisitor {
+        type Value = Key;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a DreamBerd index")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            match value.trim().parse::<f32>() {
+                Ok(index) => Self::check(index),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+            }
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            Self::check(value as f32)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            Self::check(value as f32)
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+            Self::check(value as f32)
+        }
+    }
+}
@@ -0,0 +1,669 @@
+//! Async streams, sinks and channels on top of `List`, using only std's `Future` and `Waker`.
+//!
+//! [`Stream`] and [`Sink`] have the same shape as the traits of the `futures` crate,
+//! so adapting them is a one-line `poll_*` call.
+//! A list's [`IntoIter`] is a stream, a [`List`] is a sink that pushes,
+//! and [`async_channel`] connects tasks through a bounded `List` buffer.
+//! [`block_on`] is a minimal executor that runs a future on the current thread.
+//!
+//! # Example
+//! ```
+//! use std::thread;
+//! use dreamberd_array::stream::{async_channel, block_on, Stream};
+//!
+//! let (tx, mut rx) = async_channel(2);
+//! let producer = thread::spawn(move || {
+//!     block_on(async {
+//!         for word in ["one", "two", "three"] {
+//!             tx.send(word).await.unwrap();
+//!         }
+//!     })
+//! });
+//!
+//! let received = block_on(async {
+//!     let mut received = Vec::new();
+//!     while let Some(word) = rx.next().await {
+//!         received.push(word);
+//!     }
+//!     received
+//! });
+//! producer.join().unwrap();
+//!
+//! assert_eq!(received, vec!["one", "two", "three"]);
+//! ```
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::{pin, Pin};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use crate::{IntoIter, List};
+
+/// An asynchronous series of values, like an async [`Iterator`].
+pub trait Stream {
+    /// The values the stream yields.
+    type Item;
+
+    /// Polls for the next value, returning `Ready(None)` once the stream is finished.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+
+    /// Returns a future resolving to the next value.
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin + Sized,
+    {
+        Next { stream: self }
+    }
+}
+
+/// Something values can be sent into asynchronously.
+pub trait Sink<Item> {
+    /// The error returned when the sink cannot take any more values.
+    type Error;
+
+    /// Polls until the sink can take a value.
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Starts sending a value. Must only be called after `poll_ready` returned `Ready(Ok(()))`.
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error>;
+
+    /// Polls until every value sent so far has arrived.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Polls until the sink is flushed and closed.
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Returns a future that waits until the sink is ready and starts sending a value.
+    ///
+    /// Like `futures`' `feed`, it does not flush the sink; call `poll_flush` for that.
+    fn feed(&mut self, item: Item) -> Feed<'_, Self, Item>
+    where
+        Self: Unpin + Sized,
+    {
+        Feed {
+            sink: self,
+            item: Some(item),
+        }
+    }
+}
+
+/// The future returned by [`Stream::next`].
+pub struct Next<'a, S> {
+    stream: &'a mut S,
+}
+
+impl<S: Stream + Unpin> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.get_mut().stream).poll_next(cx)
+    }
+}
+
+/// The future returned by [`Sink::feed`].
+pub struct Feed<'a, S, Item> {
+    sink: &'a mut S,
+    item: Option<Item>,
+}
+
+// The item is only ever moved, never pinned.
+impl<S, Item> Unpin for Feed<'_, S, Item> {}
+
+impl<S: Sink<Item> + Unpin, Item> Future for Feed<'_, S, Item> {
+    type Output = Result<(), S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match Pin::new(&mut *this.sink).poll_ready(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        }
+        let item = this.item.take().expect("`Feed` polled after completion");
+        Poll::Ready(Pin::new(&mut *this.sink).start_send(item))
+    }
+}
+
+/// Yields the elements of the list, starting at the highest index.
+///
+/// `IntoIter` is also an [`Iterator`], so `next` has to be called as `Stream::next`.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::stream::{block_on, Stream};
+/// use dreamberd_array::dlist;
+///
+/// let mut stream = dlist![1, 2].into_iter();
+///
+/// assert_eq!(block_on(Stream::next(&mut stream)), Some(2));
+/// assert_eq!(block_on(Stream::next(&mut stream)), Some(1));
+/// assert_eq!(block_on(Stream::next(&mut stream)), None);
+/// ```
+impl<T> Stream for IntoIter<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Poll::Ready(Iterator::next(self.get_mut()))
+    }
+}
+
+/// Pushes every value sent, and is always ready.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::stream::{block_on, Sink};
+/// use dreamberd_array::{dlist, List};
+///
+/// let mut list = List::new();
+/// block_on(list.feed(1)).unwrap();
+/// block_on(list.feed(2)).unwrap();
+///
+/// assert_eq!(list, dlist![1, 2]);
+/// ```
+impl<T> Sink<T> for List<T> {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Infallible> {
+        self.get_mut().push(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Wakes a thread parked in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a future to completion on the current thread, parking it while the future is pending.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::stream::block_on;
+///
+/// assert_eq!(block_on(async { 40 + 2 }), 42);
+/// ```
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+/// The error returned when sending on a channel whose receiver is gone, holding the value that was not sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sending on a closed channel")
+    }
+}
+
+impl<T: fmt::Debug> Error for SendError<T> {}
+
+/// The ways [`Sender::try_send`] can fail, holding the value that was not sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrySendError<T> {
+    /// The buffer is at capacity.
+    Full(T),
+    /// The receiver is gone.
+    Closed(T),
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "sending on a full channel"),
+            TrySendError::Closed(_) => write!(f, "sending on a closed channel"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for TrySendError<T> {}
+
+/// The ways [`Receiver::try_recv`] can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The buffer is empty, but a sender may still send.
+    Empty,
+    /// The buffer is empty and every sender is gone.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "receiving on an empty channel"),
+            TryRecvError::Closed => write!(f, "receiving on a closed channel"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+struct Shared<T> {
+    /// Newly sent values, the newest at the highest index.
+    inbox: List<T>,
+    /// Values ready to be received, the oldest at the highest index.
+    outbox: List<T>,
+    len: usize,
+    /// Slots promised to senders by [`Sink::poll_ready`].
+    reserved: usize,
+    capacity: usize,
+    senders: usize,
+    receiver: bool,
+    recv_waker: Option<Waker>,
+    /// Waiting senders, in the order they started waiting.
+    send_wakers: VecDeque<Waker>,
+    /// Tasks to wake once the lock is released.
+    wakeups: Wakeups,
+}
+
+/// The tasks a locked channel has woken, which are only woken for real once the lock is released,
+/// so that no waker runs while it is held.
+#[derive(Default)]
+struct Wakeups {
+    receiver: Option<Waker>,
+    senders: VecDeque<Waker>,
+}
+
+impl Wakeups {
+    fn wake(self) {
+        if let Some(waker) = self.receiver {
+            waker.wake();
+        }
+        for waker in self.senders {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Shared<T> {
+    fn has_room(&self) -> bool {
+        self.len + self.reserved < self.capacity
+    }
+
+    fn enqueue(&mut self, item: T) {
+        self.inbox.push(item);
+        self.len += 1;
+        self.wake_receiver();
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            // Popping the inbox empty pushes its oldest value last, to the highest index.
+            while let Some(item) = self.inbox.pop() {
+                self.outbox.push(item);
+            }
+        }
+        let item = self.outbox.pop()?;
+        self.len -= 1;
+        self.wake_sender();
+        Some(item)
+    }
+
+    fn wake_receiver(&mut self) {
+        if let Some(waker) = self.recv_waker.take() {
+            self.wakeups.receiver = Some(waker);
+        }
+    }
+
+    /// Wakes the sender that has waited longest, for a slot that became free.
+    fn wake_sender(&mut self) {
+        if let Some(waker) = self.send_wakers.pop_front() {
+            self.wakeups.senders.push_back(waker);
+        }
+    }
+
+    fn wake_senders(&mut self) {
+        self.wakeups.senders.append(&mut self.send_wakers);
+    }
+}
+
+type Channel<T> = Arc<Mutex<Shared<T>>>;
+
+/// A locked channel, which wakes the tasks woken through it after letting go of the lock.
+struct Locked<'a, T>(Option<MutexGuard<'a, Shared<T>>>);
+
+impl<T> Deref for Locked<'_, T> {
+    type Target = Shared<T>;
+
+    fn deref(&self) -> &Shared<T> {
+        self.0
+            .as_deref()
+            .expect("the channel is locked until dropped")
+    }
+}
+
+impl<T> DerefMut for Locked<'_, T> {
+    fn deref_mut(&mut self) -> &mut Shared<T> {
+        self.0
+            .as_deref_mut()
+            .expect("the channel is locked until dropped")
+    }
+}
+
+impl<T> Drop for Locked<'_, T> {
+    fn drop(&mut self) {
+        if let Some(mut shared) = self.0.take() {
+            let wakeups = std::mem::take(&mut shared.wakeups);
+            drop(shared);
+            wakeups.wake();
+        }
+    }
+}
+
+// Wakers only run once the lock is released, and the only other outside code that runs under it
+// clones or drops a waker after the channel is consistent again, so a poisoned lock is still usable.
+fn lock<T>(channel: &Channel<T>) -> Locked<'_, T> {
+    Locked(Some(channel.lock().unwrap_or_else(PoisonError::into_inner)))
+}
+
+/// Creates a channel that buffers at most `capacity` values in a [`List`].
+///
+/// Values arrive in the order they were sent.
+/// A sender waits while the buffer is full, and the receiver waits while it is empty.
+///
+/// # Panics
+/// Panics if `capacity` is `0`.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::stream::{async_channel, TrySendError};
+///
+/// let (tx, rx) = async_channel(1);
+/// tx.try_send(1).unwrap();
+///
+/// assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+/// assert_eq!(rx.try_recv(), Ok(1));
+/// ```
+pub fn async_channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "channel capacity must be at least 1");
+    let channel = Arc::new(Mutex::new(Shared {
+        inbox: List::new(),
+        outbox: List::new(),
+        len: 0,
+        reserved: 0,
+        capacity,
+        senders: 1,
+        receiver: true,
+        recv_waker: None,
+        send_wakers: VecDeque::new(),
+        wakeups: Wakeups::default(),
+    }));
+    (
+        Sender {
+            channel: Arc::clone(&channel),
+            reserved: false,
+        },
+        Receiver { channel },
+    )
+}
+
+/// The sending half of an [`async_channel`]. Clone it to send from several tasks.
+pub struct Sender<T> {
+    channel: Channel<T>,
+    /// Whether this sender holds a slot from [`Sink::poll_ready`].
+    reserved: bool,
+}
+
+impl<T> Sender<T> {
+    /// Returns a future that waits for room in the buffer and sends `item`.
+    ///
+    /// Fails with the item if the receiver is gone.
+    pub fn send(&self, item: T) -> SendFuture<'_, T> {
+        SendFuture {
+            sender: self,
+            item: Some(item),
+        }
+    }
+
+    /// Sends `item` if there is room in the buffer right now.
+    pub fn try_send(&self, item: T) -> Result<(), TrySendError<T>> {
+        let mut shared = lock(&self.channel);
+        if !shared.receiver {
+            Err(TrySendError::Closed(item))
+        } else if !shared.has_room() {
+            Err(TrySendError::Full(item))
+        } else {
+            shared.enqueue(item);
+            Ok(())
+        }
+    }
+
+    /// Returns `true` if the receiver is gone.
+    pub fn is_closed(&self) -> bool {
+        !lock(&self.channel).receiver
+    }
+
+    /// Returns how many values the buffer holds at most.
+    pub fn capacity(&self) -> usize {
+        lock(&self.channel).capacity
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        lock(&self.channel).senders += 1;
+        Sender {
+            channel: Arc::clone(&self.channel),
+            reserved: false,
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.channel);
+        shared.senders -= 1;
+        if self.reserved {
+            shared.reserved -= 1;
+        }
+        // Whether it held a slot or was woken for one, pass it on.
+        if shared.has_room() {
+            shared.wake_sender();
+        }
+        if shared.senders == 0 {
+            shared.wake_receiver();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// `poll_ready` waits for room in the buffer and reserves it for the next `start_send`.
+/// It is always ready once the receiver is gone, so that `start_send` can fail with the item.
+impl<T> Sink<T> for Sender<T> {
+    type Error = SendError<T>;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), SendError<T>>> {
+        let this = self.get_mut();
+        let mut shared = lock(&this.channel);
+        if this.reserved || !shared.receiver {
+            return Poll::Ready(Ok(()));
+        }
+        if shared.has_room() {
+            shared.reserved += 1;
+            this.reserved = true;
+            return Poll::Ready(Ok(()));
+        }
+        if !shared
+            .send_wakers
+            .iter()
+            .any(|waker| waker.will_wake(cx.waker()))
+        {
+            shared.send_wakers.push_back(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), SendError<T>> {
+        let this = self.get_mut();
+        let mut shared = lock(&this.channel);
+        if std::mem::take(&mut this.reserved) {
+            shared.reserved -= 1;
+        }
+        if !shared.receiver {
+            return Err(SendError(item));
+        }
+        shared.enqueue(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), SendError<T>>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), SendError<T>>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The future returned by [`Sender::send`].
+pub struct SendFuture<'a, T> {
+    sender: &'a Sender<T>,
+    item: Option<T>,
+}
+
+// The item is only ever moved, never pinned.
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut shared = lock(&this.sender.channel);
+        let item = this
+            .item
+            .take()
+            .expect("`SendFuture` polled after completion");
+        if !shared.receiver {
+            return Poll::Ready(Err(SendError(item)));
+        }
+        if shared.has_room() {
+            shared.enqueue(item);
+            return Poll::Ready(Ok(()));
+        }
+        this.item = Some(item);
+        if !shared
+            .send_wakers
+            .iter()
+            .any(|waker| waker.will_wake(cx.waker()))
+        {
+            shared.send_wakers.push_back(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for SendFuture<'_, T> {
+    fn drop(&mut self) {
+        // A future dropped while waiting may have been the one woken for a free slot, so pass the wakeup on.
+        if self.item.is_some() {
+            let mut shared = lock(&self.sender.channel);
+            if shared.has_room() {
+                shared.wake_sender();
+            }
+        }
+    }
+}
+
+/// The receiving half of an [`async_channel`].
+pub struct Receiver<T> {
+    channel: Channel<T>,
+}
+
+impl<T> Receiver<T> {
+    /// Returns a future resolving to the next value, or `None` once every sender is gone and the buffer is empty.
+    pub fn recv(&mut self) -> Next<'_, Self> {
+        self.next()
+    }
+
+    /// Receives a value if one is buffered right now.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut shared = lock(&self.channel);
+        match shared.dequeue() {
+            Some(item) => Ok(item),
+            None if shared.senders == 0 => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Returns how many values are buffered.
+    pub fn len(&self) -> usize {
+        lock(&self.channel).len
+    }
+
+    /// Returns `true` if no values are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.channel);
+        shared.receiver = false;
+        shared.wake_senders();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut shared = lock(&self.channel);
+        if let Some(item) = shared.dequeue() {
+            return Poll::Ready(Some(item));
+        }
+        if shared.senders == 0 {
+            return Poll::Ready(None);
+        }
+        shared.recv_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
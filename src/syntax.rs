@@ -0,0 +1,571 @@
+//! Reading and writing DreamBerd array literals.
+//!
+//! [`parse`] reads a single array literal such as `[3, 'two', maybe]!` into a `List<Value>`,
+//! [`parse_declaration`] reads a whole `const const scores = [3, 2, 5]!` line.
+//! [`to_literal`] writes a list of numbers, strings, booleans or [`Value`]s back out.
+//!
+//! Elements can be numbers, strings, nested arrays, `true`, `false`, `maybe` and `undefined`.
+//! The first element of a literal ends up at index -1, just like in DreamBerd.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::syntax;
+//! use dreamberd_array::Value;
+//!
+//! let list = syntax::parse("[3, 'two', [1]]!").unwrap();
+//! assert_eq!(list[-1.0], Value::Number(3.0));
+//! assert_eq!(list[0.0], Value::String("two".to_string()));
+//! assert_eq!(syntax::to_literal(&list), r#"[3, "two", [1]]"#);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::value::{write_string, Bool};
+use crate::{List, Value};
+
+/// What went wrong while parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// The input ended in the middle of a literal.
+    UnexpectedEnd,
+    /// A character that is not allowed at this point.
+    UnexpectedChar(char),
+    /// A string without its closing quote.
+    UnterminatedString,
+    /// An escape sequence that is not known.
+    InvalidEscape(char),
+    /// Something that looked like a number but is not one.
+    InvalidNumber(String),
+    /// Something other than `const` or `var` in a declaration.
+    InvalidKeyword(String),
+    /// A bare word other than `true`, `false`, `maybe` or `undefined`.
+    UnknownWord(String),
+    /// Arrays nested deeper than [`MAX_DEPTH`].
+    NestingTooDeep,
+}
+
+/// How deeply arrays may nest inside each other.
+///
+/// The parser recurses once per level, so deeper input fails with [`ParseErrorKind::NestingTooDeep`]
+/// instead of running out of stack.
+pub const MAX_DEPTH: usize = 256;
+
+/// A parse error, with the 1-based line and column where it was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            ParseErrorKind::InvalidEscape(c) => write!(f, "invalid escape \\{}", c),
+            ParseErrorKind::InvalidNumber(number) => write!(f, "invalid number {:?}", number),
+            ParseErrorKind::InvalidKeyword(word) => {
+                write!(f, "expected `const` or `var`, found {:?}", word)
+            }
+            ParseErrorKind::UnknownWord(word) => write!(f, "unknown word {:?}", word),
+            ParseErrorKind::NestingTooDeep => {
+                write!(f, "arrays nested more than {} deep", MAX_DEPTH)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// `const` or `var`, the two DreamBerd declaration keywords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Const,
+    Var,
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Keyword::Const => write!(f, "const"),
+            Keyword::Var => write!(f, "var"),
+        }
+    }
+}
+
+/// How a statement ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Terminator {
+    /// One or more `!`.
+    Bang(usize),
+    /// A single `?`.
+    Question,
+}
+
+impl fmt::Display for Terminator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Terminator::Bang(count) => write!(f, "{}", "!".repeat(*count)),
+            Terminator::Question => write!(f, "?"),
+        }
+    }
+}
+
+/// A declaration like `const var scores = [3, 2, 5]!`.
+///
+/// The first keyword decides whether the name can be reassigned,
+/// the second one whether the contents can change.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::syntax::{self, Keyword, Terminator};
+///
+/// let decl = syntax::parse_declaration("const var scores = [3, 2, 5]!!").unwrap();
+/// assert_eq!(decl.binding, Keyword::Const);
+/// assert_eq!(decl.contents, Keyword::Var);
+/// assert_eq!(decl.name, "scores");
+/// assert_eq!(decl.list.len(), 3);
+/// assert_eq!(decl.terminator, Some(Terminator::Bang(2)));
+/// assert_eq!(decl.to_string(), "const var scores = [3, 2, 5]!!");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    pub binding: Keyword,
+    pub contents: Keyword,
+    pub name: String,
+    pub list: List<Value>,
+    pub terminator: Option<Terminator>,
+}
+
+impl fmt::Display for Declaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} = {}", self.binding, self.contents, self.name, self.list)?;
+        if let Some(terminator) = self.terminator {
+            write!(f, "{}", terminator)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a single array literal, optionally followed by `!`s or a `?`.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::syntax;
+/// use dreamberd_array::Value;
+///
+/// let list = syntax::parse("[1, [2.5, -3]]!").unwrap();
+/// assert_eq!(list[-1.0], Value::Number(1.0));
+///
+/// let err = syntax::parse("[1,\n  2 3]").unwrap_err();
+/// assert_eq!((err.line, err.column), (2, 5));
+/// ```
+pub fn parse(src: &str) -> Result<List<Value>, ParseError> {
+    let mut parser = Parser::new(src);
+    let list = parser.array()?;
+    parser.terminator()?;
+    parser.end()?;
+    Ok(list)
+}
+
+/// Parses a single value, such as `42`, `'text'`, `maybe` or `[1, 2]`.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::syntax;
+/// use dreamberd_array::Value;
+///
+/// assert_eq!(syntax::parse_value(" 'hi' ").unwrap(), Value::from("hi"));
+/// assert_eq!(syntax::parse_value("[1]").unwrap(), Value::from(vec![1]));
+/// ```
+pub fn parse_value(src: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser::new(src);
+    let value = parser.value()?;
+    parser.end()?;
+    Ok(value)
+}
+
+/// Parses a `const const name = [...]!` declaration.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::syntax;
+///
+/// let decl = syntax::parse_declaration("var const xs = []?").unwrap();
+/// assert_eq!(decl.name, "xs");
+/// assert!(decl.list.is_empty());
+/// ```
+pub fn parse_declaration(src: &str) -> Result<Declaration, ParseError> {
+    let mut parser = Parser::new(src);
+    let binding = parser.keyword()?;
+    let contents = parser.keyword()?;
+    let name = parser.word()?;
+    parser.expect('=')?;
+    let list = parser.array()?;
+    let terminator = parser.terminator()?;
+    parser.end()?;
+    Ok(Declaration {
+        binding,
+        contents,
+        name,
+        list,
+        terminator,
+    })
+}
+
+/// Writes a list as a DreamBerd array literal, starting at index -1.
+///
+/// Strings are quoted and escaped, so a list of strings, booleans, finite numbers and such lists
+/// parses back into the same elements.
+/// Non-finite numbers and [`Value::Map`]s are written out too, but have no literal that parses back.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::{dlist, syntax};
+///
+/// assert_eq!(syntax::to_literal(&dlist![3, 2, 5]), "[3, 2, 5]");
+/// assert_eq!(syntax::to_literal(&dlist!["a", "b\"c"]), r#"["a", "b\"c"]"#);
+/// ```
+pub fn to_literal<T: Literal>(list: &List<T>) -> String {
+    list.to_literal_string()
+}
+
+/// Elements that [`to_literal`] can write as a DreamBerd literal.
+pub trait Literal {
+    /// Writes the element the way the parser reads it back.
+    fn fmt_literal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Returns the element as a literal.
+    fn to_literal_string(&self) -> String {
+        struct AsLiteral<'a, T: ?Sized>(&'a T);
+        impl<T: Literal + ?Sized> fmt::Display for AsLiteral<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_literal(f)
+            }
+        }
+        AsLiteral(self).to_string()
+    }
+}
+
+macro_rules! impl_literal_display {
+    ($($ty:ty),*) => {
+        $(
+            impl Literal for $ty {
+                fn fmt_literal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", self)
+                }
+            }
+        )*
+    };
+}
+
+impl_literal_display!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, Value
+);
+
+impl Literal for str {
+    fn fmt_literal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_string(f, self)
+    }
+}
+
+impl Literal for String {
+    fn fmt_literal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_string(f, self)
+    }
+}
+
+impl Literal for char {
+    fn fmt_literal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_string(f, self.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl<T: Literal + ?Sized> Literal for &T {
+    fn fmt_literal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt_literal(f)
+    }
+}
+
+/// Nested lists are written as nested literals.
+impl<T: Literal> Literal for List<T> {
+    fn fmt_literal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, elem) in self.index_order().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            elem.fmt_literal(f)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Reads characters while keeping track of the line and column, and scans the string and number
+/// literals that [`parse`] and the [`lang`](crate::lang) lexer share.
+pub(crate) struct Scanner<'a> {
+    chars: Peekable<Chars<'a>>,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub(crate) fn new(src: &'a str) -> Self {
+        Scanner {
+            chars: src.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub(crate) fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            kind,
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    /// The character after the next one.
+    pub(crate) fn peek_second(&self) -> Option<char> {
+        let mut ahead = self.chars.clone();
+        ahead.next();
+        ahead.next()
+    }
+
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Scans a string in single or double quotes, starting at the opening quote.
+    pub(crate) fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.error(ParseErrorKind::UnterminatedString);
+        let quote = self.bump().unwrap();
+        let mut out = String::new();
+        loop {
+            let escape = self.error(ParseErrorKind::UnexpectedEnd);
+            match self.bump() {
+                None => return Err(start),
+                Some(c) if c == quote => return Ok(out),
+                Some('\\') => {
+                    match self.bump() {
+                        Some('n') => out.push('\n'),
+                        Some('t') => out.push('\t'),
+                        Some('r') => out.push('\r'),
+                        Some('0') => out.push('\0'),
+                        Some(c @ ('\\' | '"' | '\'')) => out.push(c),
+                        Some(c) => {
+                            return Err(ParseError {
+                                kind: ParseErrorKind::InvalidEscape(c),
+                                ..escape
+                            })
+                        }
+                        None => return Err(start),
+                    }
+                }
+                Some(c) => out.push(c),
+            }
+        }
+    }
+
+    /// Scans a finite number such as `-1.5`, `1_000` or `2.5e-3`, with an optional leading sign.
+    pub(crate) fn number(&mut self) -> Result<f64, ParseError> {
+        let start = self.error(ParseErrorKind::UnexpectedEnd);
+        let mut text = String::new();
+        while let Some(c) = self
+            .peek()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+' | '_'))
+        {
+            // A sign is only part of the number at the start or right after an exponent.
+            if matches!(c, '-' | '+') && !(text.is_empty() || text.ends_with(['e', 'E'])) {
+                break;
+            }
+            text.push(c);
+            self.bump();
+        }
+        // `inf` and `nan` parse as floats, but cannot be written back out as numbers.
+        match text.replace('_', "").parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(number),
+            _ => Err(ParseError {
+                kind: ParseErrorKind::InvalidNumber(text),
+                ..start
+            }),
+        }
+    }
+}
+
+struct Parser<'a> {
+    scanner: Scanner<'a>,
+    /// How many arrays the parser is inside of.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Parser {
+            scanner: Scanner::new(src),
+            depth: 0,
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.scanner.error(kind)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.scanner.peek()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        self.scanner.bump()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Skips whitespace and returns the next character without consuming it.
+    fn next_token(&mut self) -> Result<char, ParseError> {
+        self.skip_whitespace();
+        self.peek().ok_or_else(|| self.error(ParseErrorKind::UnexpectedEnd))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        let c = self.next_token()?;
+        if c != expected {
+            return Err(self.error(ParseErrorKind::UnexpectedChar(c)));
+        }
+        self.bump();
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) => Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+            None => Ok(()),
+        }
+    }
+
+    fn word(&mut self) -> Result<String, ParseError> {
+        let c = self.next_token()?;
+        if !is_word_char(c) {
+            return Err(self.error(ParseErrorKind::UnexpectedChar(c)));
+        }
+        let mut word = String::new();
+        while let Some(c) = self.peek().filter(|c| is_word_char(*c)) {
+            word.push(c);
+            self.bump();
+        }
+        Ok(word)
+    }
+
+    fn keyword(&mut self) -> Result<Keyword, ParseError> {
+        self.skip_whitespace();
+        let err = self.error(ParseErrorKind::UnexpectedEnd);
+        match self.word()?.as_str() {
+            "const" => Ok(Keyword::Const),
+            "var" => Ok(Keyword::Var),
+            other => Err(ParseError {
+                kind: ParseErrorKind::InvalidKeyword(other.to_string()),
+                ..err
+            }),
+        }
+    }
+
+    fn terminator(&mut self) -> Result<Option<Terminator>, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('!') => {
+                let mut count = 0;
+                while self.peek() == Some('!') {
+                    self.bump();
+                    count += 1;
+                }
+                Ok(Some(Terminator::Bang(count)))
+            }
+            Some('?') => {
+                self.bump();
+                Ok(Some(Terminator::Question))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        match self.next_token()? {
+            '[' => self.array().map(Value::List),
+            '"' | '\'' => self.scanner.string().map(Value::String),
+            c if c == '-' || c == '+' || c == '.' || c.is_ascii_digit() => {
+                self.scanner.number().map(Value::Number)
+            }
+            c if c.is_alphabetic() => {
+                let start = self.error(ParseErrorKind::UnexpectedEnd);
+                match self.word()?.as_str() {
+                    "true" => Ok(Value::Bool(Bool::True)),
+                    "false" => Ok(Value::Bool(Bool::False)),
+                    "maybe" => Ok(Value::Bool(Bool::Maybe)),
+                    "undefined" => Ok(Value::Undefined),
+                    other => Err(ParseError {
+                        kind: ParseErrorKind::UnknownWord(other.to_string()),
+                        ..start
+                    }),
+                }
+            }
+            c => Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+        }
+    }
+
+    fn array(&mut self) -> Result<List<Value>, ParseError> {
+        self.next_token()?;
+        let too_deep = self.error(ParseErrorKind::NestingTooDeep);
+        self.expect('[')?;
+        if self.depth == MAX_DEPTH {
+            return Err(too_deep);
+        }
+        self.depth += 1;
+        let mut elems = Vec::new();
+        loop {
+            if self.next_token()? == ']' {
+                self.bump();
+                break;
+            }
+            elems.push(self.value()?);
+            match self.next_token()? {
+                ',' => {
+                    self.bump();
+                }
+                ']' => {
+                    self.bump();
+                    break;
+                }
+                c => return Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+            }
+        }
+        self.depth -= 1;
+        Ok(List::from(elems))
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '=' | '[' | ']' | ',' | '!' | '?' | '"' | '\'')
+}
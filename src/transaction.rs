@@ -0,0 +1,198 @@
+//! All-or-nothing edits with [`List::transaction`].
+//!
+//! The closure gets a [`Transaction`] with the mutating methods of the list.
+//! Every change is journaled the same way [`crate::undo::UndoableList`] does it,
+//! and reversed if the closure returns `Err` or panics.
+//! The list is borrowed for the whole transaction, so nothing else can see it half-applied.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::dlist;
+//!
+//! let mut list = dlist![1, 2, 3, 4];
+//! let result: Result<(), &str> = list.transaction(|tx| {
+//!     tx.remove(1.5);
+//!     tx.insert(-0.5, 42);
+//!     tx.set(0.0, 7).ok_or("index 0 is gone")?;
+//!     Err("changed my mind")
+//! });
+//!
+//! assert_eq!(result, Err("changed my mind"));
+//! assert_eq!(list, dlist![1, 2, 3, 4]);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+use crate::undo::Edit;
+use crate::{Iter, List};
+
+/// The handle a [`List::transaction`] closure changes the list through.
+pub struct Transaction<'a, T> {
+    list: &'a mut List<T>,
+    /// How to reverse every change so far, in the order they were made.
+    journal: Vec<Edit<T>>,
+}
+
+impl<T> List<T> {
+    /// Runs `f` as a transaction: its changes are kept if it returns `Ok`,
+    /// and rolled back if it returns `Err` or panics.
+    ///
+    /// A panic is passed on after the rollback.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    ///
+    /// let mut list = dlist![1, 2, 3];
+    /// let len = list.transaction(|tx| {
+    ///     tx.push(4);
+    ///     tx.insert(-0.5, 42);
+    ///     Ok::<_, ()>(tx.len())
+    /// });
+    ///
+    /// assert_eq!(len, Ok(5));
+    /// assert_eq!(list, dlist![1, 42, 2, 3, 4]);
+    /// ```
+    pub fn transaction<R, E, F>(&mut self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Transaction<'_, T>) -> Result<R, E>,
+    {
+        let mut tx = Transaction {
+            list: self,
+            journal: Vec::new(),
+        };
+        let result = f(&mut tx);
+        if result.is_ok() {
+            tx.journal.clear();
+        }
+        // Dropping the transaction rolls back whatever is left in the journal, also while unwinding.
+        result
+    }
+}
+
+impl<T> Transaction<'_, T> {
+    /// Pushes an element, like [`List::push`].
+    pub fn push(&mut self, elem: T) {
+        self.apply(Edit::Push(elem));
+    }
+
+    /// Inserts an element at an index, like [`List::insert`].
+    pub fn insert(&mut self, index: f32, elem: T) {
+        self.apply(Edit::Insert(index, elem));
+    }
+
+    /// Returns the element at an index, like [`List::get`].
+    pub fn get(&self, index: f32) -> Option<&T> {
+        self.list.get(index)
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns an iterator over the list, like [`List::iter`].
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Returns the list as the transaction has changed it so far.
+    pub fn as_list(&self) -> &List<T> {
+        self.list
+    }
+
+    fn apply(&mut self, edit: Edit<T>) -> Option<&T> {
+        let inverse = edit.apply(self.list)?;
+        self.journal.push(inverse);
+        self.journal.last()?.elem()
+    }
+}
+
+/// Taking elements out needs `T: Clone`, since the journal keeps the original until the transaction ends,
+/// in case it has to be put back.
+impl<T: Clone> Transaction<'_, T> {
+    /// Pops the first element, like [`List::pop`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    ///
+    /// let mut list = dlist![1, 2];
+    /// let popped = list.transaction(|tx| Ok::<_, ()>(tx.pop().unwrap()));
+    ///
+    /// assert_eq!(popped, Ok(2));
+    /// assert_eq!(list, dlist![1]);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.apply(Edit::Pop).cloned()
+    }
+
+    /// Removes the element at an index, like [`List::remove`].
+    pub fn remove(&mut self, index: f32) -> Option<T> {
+        self.apply(Edit::Remove(index)).cloned()
+    }
+
+    /// Overwrites the element at an index, returning the old one.
+    pub fn set(&mut self, index: f32, elem: T) -> Option<T> {
+        self.apply(Edit::Replace(index, elem)).cloned()
+    }
+
+    /// Returns a mutable reference to the element at an index, like [`List::get_mut`].
+    ///
+    /// The current value is copied first, so it can be restored on rollback.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    ///
+    /// let mut list = dlist![1, 2];
+    /// let result = list.transaction(|tx| {
+    ///     *tx.get_mut(0.0).unwrap() += 40;
+    ///     tx[-1.0] = 0;
+    ///     Err::<(), _>(tx[0.0])
+    /// });
+    ///
+    /// assert_eq!(result, Err(42));
+    /// assert_eq!(list, dlist![1, 2]);
+    /// ```
+    pub fn get_mut(&mut self, index: f32) -> Option<&mut T> {
+        let old = self.list.get(index)?.clone();
+        self.apply(Edit::Replace(index, old));
+        self.list.get_mut(index)
+    }
+}
+
+impl<T> Drop for Transaction<'_, T> {
+    fn drop(&mut self) {
+        while let Some(edit) = self.journal.pop() {
+            edit.apply(self.list);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Transaction<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.list, f)
+    }
+}
+
+impl<T> Index<f32> for Transaction<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: f32) -> &T {
+        &self.list[index]
+    }
+}
+
+impl<T: Clone> IndexMut<f32> for Transaction<'_, T> {
+    fn index_mut(&mut self, index: f32) -> &mut T {
+        self.get_mut(index).unwrap()
+    }
+}
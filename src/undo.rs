@@ -0,0 +1,459 @@
+//! Lists with undo and redo.
+//!
+//! An [`UndoableList`] keeps a journal of how to reverse every change instead of snapshots,
+//! so `push`, `pop`, `insert`, `remove` and `set` work for any `T`.
+//! Only changing an element in place through `get_mut` or `IndexMut` needs `T: Clone`,
+//! since the old value has to be copied before it is handed out.
+//!
+//! Changes between [`UndoableList::begin_group`] and [`UndoableList::end_group`] are undone and redone together.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::dlist;
+//! use dreamberd_array::undo::UndoableList;
+//!
+//! let mut list = UndoableList::new();
+//! list.push('a');
+//! list.begin_group();
+//! list.push('b');
+//! list.push('c');
+//! list.end_group();
+//!
+//! assert!(list.undo());
+//! assert_eq!(list.as_list(), &dlist!['a']);
+//! assert!(list.redo());
+//! assert_eq!(list.as_list(), &dlist!['a', 'b', 'c']);
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+use crate::{Iter, List};
+
+/// A single change, kept in the journal as the way to reverse another one.
+pub(crate) enum Edit<T> {
+    Push(T),
+    Pop,
+    Insert(f32, T),
+    Remove(f32),
+    Replace(f32, T),
+}
+
+impl<T> Edit<T> {
+    /// Applies the change and returns the one reversing it, or `None` if nothing changed.
+    pub(crate) fn apply(self, list: &mut List<T>) -> Option<Edit<T>> {
+        match self {
+            Edit::Push(elem) => {
+                list.push(elem);
+                Some(Edit::Pop)
+            }
+            Edit::Pop => list.pop().map(Edit::Push),
+            Edit::Insert(index, elem) => {
+                list.get(index)?;
+                list.insert(index, elem);
+                Some(Edit::Remove(crate::ceil(index)))
+            }
+            Edit::Remove(index) => {
+                let elem = list.remove(index)?;
+                let index = crate::ceil(index);
+                // The element with the highest index can only come back with `push`.
+                if index > list.len() as f32 - 2.0 {
+                    Some(Edit::Push(elem))
+                } else {
+                    Some(Edit::Insert(index, elem))
+                }
+            }
+            Edit::Replace(index, elem) => {
+                let slot = list.get_mut(index)?;
+                Some(Edit::Replace(crate::ceil(index), core::mem::replace(slot, elem)))
+            }
+        }
+    }
+
+    /// Returns the element the change carries.
+    pub(crate) fn elem(&self) -> Option<&T> {
+        match self {
+            Edit::Push(elem) | Edit::Insert(_, elem) | Edit::Replace(_, elem) => Some(elem),
+            Edit::Pop | Edit::Remove(_) => None,
+        }
+    }
+}
+
+/// A `List` that can undo and redo its changes.
+pub struct UndoableList<T> {
+    list: List<T>,
+    /// Every step is a group of reversing changes, in the order the changes were made.
+    undo: VecDeque<Vec<Edit<T>>>,
+    redo: Vec<Vec<Edit<T>>>,
+    /// The changes of the open group.
+    group: Vec<Edit<T>>,
+    depth: usize,
+    limit: Option<usize>,
+}
+
+impl<T> UndoableList<T> {
+    /// Creates an empty list that can undo every change.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let list: UndoableList<i32> = UndoableList::new();
+    ///
+    /// assert!(!list.can_undo());
+    /// ```
+    pub fn new() -> Self {
+        UndoableList {
+            list: List::new(),
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            group: Vec::new(),
+            depth: 0,
+            limit: None,
+        }
+    }
+
+    /// Creates an empty list that can undo at most `limit` steps, forgetting the oldest ones.
+    ///
+    /// # Panics
+    /// Panics if `limit` is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::with_limit(1);
+    /// list.push(1);
+    /// list.push(2);
+    ///
+    /// assert!(list.undo());
+    /// assert!(!list.undo());
+    /// assert_eq!(list.len(), 1);
+    /// ```
+    pub fn with_limit(limit: usize) -> Self {
+        assert!(limit > 0, "the undo limit must be at least 1");
+        UndoableList {
+            limit: Some(limit),
+            ..UndoableList::new()
+        }
+    }
+
+    /// Pushes an element, like [`List::push`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::new();
+    /// list.push(1);
+    /// list.undo();
+    ///
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn push(&mut self, elem: T) {
+        self.apply(Edit::Push(elem));
+    }
+
+    /// Pops the first element, like [`List::pop`].
+    ///
+    /// The element stays in the journal so it can come back,
+    /// which is why only a reference to it is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::new();
+    /// list.push(1);
+    ///
+    /// assert_eq!(list.pop(), Some(&1));
+    /// assert!(list.undo());
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// ```
+    pub fn pop(&mut self) -> Option<&T> {
+        self.apply(Edit::Pop)
+    }
+
+    /// Inserts an element at an index, like [`List::insert`].
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::from(dlist![1, 2]);
+    /// list.insert(-0.5, 42);
+    ///
+    /// assert_eq!(list.as_list(), &dlist![1, 42, 2]);
+    /// list.undo();
+    /// assert_eq!(list.as_list(), &dlist![1, 2]);
+    /// ```
+    pub fn insert(&mut self, index: f32, elem: T) {
+        self.apply(Edit::Insert(index, elem));
+    }
+
+    /// Removes the element at an index, like [`List::remove`], returning a reference to it.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::from(dlist![1, 2, 3]);
+    ///
+    /// assert_eq!(list.remove(0.0), Some(&2));
+    /// list.undo();
+    /// assert_eq!(list.as_list(), &dlist![1, 2, 3]);
+    /// ```
+    pub fn remove(&mut self, index: f32) -> Option<&T> {
+        self.apply(Edit::Remove(index))
+    }
+
+    /// Overwrites the element at an index, returning a reference to the old one.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::from(dlist![1, 2]);
+    ///
+    /// assert_eq!(list.set(-1.0, 42), Some(&1));
+    /// assert_eq!(list.set(5.0, 42), None);
+    /// list.undo();
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// ```
+    pub fn set(&mut self, index: f32, elem: T) -> Option<&T> {
+        self.apply(Edit::Replace(index, elem))
+    }
+
+    /// Returns the element at an index, like [`List::get`].
+    pub fn get(&self, index: f32) -> Option<&T> {
+        self.list.get(index)
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns an iterator over the list, like [`List::iter`].
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Returns the current elements.
+    pub fn as_list(&self) -> &List<T> {
+        &self.list
+    }
+
+    /// Returns the current elements, dropping the journal.
+    pub fn into_list(self) -> List<T> {
+        self.list
+    }
+
+    /// Starts a group of changes that are undone and redone as one step.
+    ///
+    /// Groups can be nested; only the outermost one becomes a step.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::new();
+    /// list.begin_group();
+    /// list.push(1);
+    /// list.begin_group();
+    /// list.push(2);
+    /// list.end_group();
+    /// list.end_group();
+    ///
+    /// list.undo();
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn begin_group(&mut self) {
+        self.depth += 1;
+    }
+
+    /// Ends the group started by the matching [`UndoableList::begin_group`].
+    ///
+    /// Does nothing if no group is open. A group without changes does not become a step.
+    pub fn end_group(&mut self) {
+        if self.depth == 0 {
+            return;
+        }
+        self.depth -= 1;
+        if self.depth == 0 && !self.group.is_empty() {
+            let step = core::mem::take(&mut self.group);
+            self.push_undo(step);
+        }
+    }
+
+    /// Reverses the last step, returning `false` if there was nothing to undo.
+    ///
+    /// Open groups are ended first.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::new();
+    /// list.push(1);
+    ///
+    /// assert!(list.undo());
+    /// assert!(!list.undo());
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        self.close_groups();
+        let Some(step) = self.undo.pop_back() else {
+            return false;
+        };
+        let step = self.replay(step);
+        self.redo.push(step);
+        true
+    }
+
+    /// Repeats the last undone step, returning `false` if there was nothing to redo.
+    ///
+    /// Any change made after an undo clears what can be redone.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::new();
+    /// list.push(1);
+    /// list.undo();
+    ///
+    /// assert!(list.redo());
+    /// assert_eq!(list.get(-1.0), Some(&1));
+    /// assert!(!list.redo());
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        self.close_groups();
+        let Some(step) = self.redo.pop() else {
+            return false;
+        };
+        let step = self.replay(step);
+        self.push_undo(step);
+        true
+    }
+
+    /// Returns `true` if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty() || !self.group.is_empty()
+    }
+
+    /// Returns `true` if there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets every step, keeping the current elements.
+    pub fn clear_history(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group.clear();
+    }
+
+    fn apply(&mut self, edit: Edit<T>) -> Option<&T> {
+        let inverse = edit.apply(&mut self.list)?;
+        self.redo.clear();
+        if self.depth > 0 {
+            self.group.push(inverse);
+            self.group.last()?.elem()
+        } else {
+            self.push_undo(vec![inverse]);
+            self.undo.back()?.last()?.elem()
+        }
+    }
+
+    fn push_undo(&mut self, step: Vec<Edit<T>>) {
+        self.undo.push_back(step);
+        if self.limit.is_some_and(|limit| self.undo.len() > limit) {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Applies a step from its last change to its first, returning the step that reverses it.
+    fn replay(&mut self, step: Vec<Edit<T>>) -> Vec<Edit<T>> {
+        step.into_iter()
+            .rev()
+            .filter_map(|edit| edit.apply(&mut self.list))
+            .collect()
+    }
+
+    fn close_groups(&mut self) {
+        while self.depth > 0 {
+            self.end_group();
+        }
+    }
+}
+
+impl<T: Clone> UndoableList<T> {
+    /// Returns a mutable reference to the element at an index, like [`List::get_mut`].
+    ///
+    /// The current value is copied into the journal first, even if it ends up unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::dlist;
+    /// use dreamberd_array::undo::UndoableList;
+    ///
+    /// let mut list = UndoableList::from(dlist![1]);
+    /// *list.get_mut(-1.0).unwrap() += 1;
+    /// list[-1.0] *= 10;
+    ///
+    /// assert_eq!(list.get(-1.0), Some(&20));
+    /// list.undo();
+    /// assert_eq!(list.get(-1.0), Some(&2));
+    /// ```
+    pub fn get_mut(&mut self, index: f32) -> Option<&mut T> {
+        let old = self.list.get(index)?.clone();
+        self.apply(Edit::Replace(index, old));
+        self.list.get_mut(index)
+    }
+}
+
+impl<T> Default for UndoableList<T> {
+    fn default() -> Self {
+        UndoableList::new()
+    }
+}
+
+impl<T> From<List<T>> for UndoableList<T> {
+    fn from(list: List<T>) -> Self {
+        UndoableList {
+            list,
+            ..UndoableList::new()
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UndoableList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.list, f)
+    }
+}
+
+impl<T> Index<f32> for UndoableList<T> {
+    type Output = T;
+
+    fn index(&self, index: f32) -> &T {
+        &self.list[index]
+    }
+}
+
+impl<T: Clone> IndexMut<f32> for UndoableList<T> {
+    fn index_mut(&mut self, index: f32) -> &mut T {
+        self.get_mut(index).unwrap()
+    }
+}
@@ -0,0 +1,339 @@
+//! Untyped DreamBerd values.
+//!
+//! DreamBerd arrays can hold anything, so a single `List<Value>` can mix numbers,
+//! strings, booleans, nested lists and maps.
+//!
+//! # Example
+//! ```
+//! use dreamberd_array::value::Bool;
+//! use dreamberd_array::{dlist, List, Value};
+//!
+//! let list: List<Value> = dlist![1.into(), "two".into(), Bool::Maybe.into(), vec![3, 4].into()];
+//!
+//! assert_eq!(list[-1.0], Value::Number(1.0));
+//! assert_eq!(Value::List(list).get_path(&[2.0, 0.0]), Some(&Value::Number(4.0)));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::List;
+
+/// A DreamBerd boolean, which can also be `maybe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bool {
+    True,
+    False,
+    Maybe,
+}
+
+impl From<bool> for Bool {
+    fn from(value: bool) -> Self {
+        if value {
+            Bool::True
+        } else {
+            Bool::False
+        }
+    }
+}
+
+impl fmt::Display for Bool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bool::True => write!(f, "true"),
+            Bool::False => write!(f, "false"),
+            Bool::Maybe => write!(f, "maybe"),
+        }
+    }
+}
+
+/// A single DreamBerd value.
+///
+/// Two values are equal if they have the same shape and contents all the way down.
+/// Unlike `f64`, a `NaN` number is equal to itself, so `Value` is `Eq`.
+#[derive(Debug, Clone, Default)]
+pub enum Value {
+    /// Any number. DreamBerd does not distinguish integers from floats.
+    Number(f64),
+    /// A string.
+    String(String),
+    /// `true`, `false` or `maybe`.
+    Bool(Bool),
+    /// The absence of a value.
+    #[default]
+    Undefined,
+    /// A nested array.
+    List(List<Value>),
+    /// String keys mapped to values.
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Follows `path` through nested lists, using `List::get` at every step.
+    ///
+    /// An empty path returns the value itself.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::Value;
+    ///
+    /// let value = Value::from(vec![vec![1, 2], vec![3, 4]]);
+    ///
+    /// assert_eq!(value.get_path(&[0.0, -1.0]), Some(&Value::Number(3.0)));
+    /// assert_eq!(value.get_path(&[-1.0, -0.5]), Some(&Value::Number(2.0)));
+    /// assert_eq!(value.get_path(&[0.0, -1.0, -1.0]), None);
+    /// assert_eq!(value.get_path(&[]), Some(&value));
+    /// ```
+    pub fn get_path(&self, path: &[f32]) -> Option<&Value> {
+        let mut current = self;
+        for index in path {
+            current = current.as_list()?.get(*index)?;
+        }
+        Some(current)
+    }
+
+    /// Follows `path` through nested lists, using `List::get_mut` at every step.
+    ///
+    /// # Example
+    /// ```
+    /// use dreamberd_array::Value;
+    ///
+    /// let mut value = Value::from(vec![vec![1, 2], vec![3, 4]]);
+    /// *value.get_path_mut(&[-1.0, 0.0]).unwrap() = Value::from("x");
+    ///
+    /// assert_eq!(value.get_path(&[-1.0, 0.0]), Some(&Value::from("x")));
+    /// ```
+    pub fn get_path_mut(&mut self, path: &[f32]) -> Option<&mut Value> {
+        let mut current = self;
+        for index in path {
+            current = current.as_list_mut()?.get_mut(*index)?;
+        }
+        Some(current)
+    }
+
+    /// Returns the number, if this is one.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if this is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, if this is one.
+    pub fn as_bool(&self) -> Option<Bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the nested list, if this is one.
+    pub fn as_list(&self) -> Option<&List<Value>> {
+        match self {
+            Value::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Returns the nested list mutably, if this is one.
+    pub fn as_list_mut(&mut self) -> Option<&mut List<Value>> {
+        match self {
+            Value::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Returns the map, if this is one.
+    pub fn as_map(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Value::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this is `undefined`.
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Value::Undefined)
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Undefined, Value::Undefined) => true,
+            (Value::List(a), Value::List(b)) => a == b,
+            (Value::Map(a), Value::Map(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+/// Formats the value as a DreamBerd literal, quoting strings.
+///
+/// # Example
+/// ```
+/// use dreamberd_array::{dlist, Value};
+///
+/// let value = Value::List(dlist![Value::Number(1.0), Value::String("a\"b".to_string())]);
+/// assert_eq!(value.to_string(), r#"[1, "a\"b"]"#);
+/// ```
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(number) => write!(f, "{}", number),
+            Value::String(string) => write_string(f, string),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Undefined => write!(f, "undefined"),
+            Value::List(list) => write!(f, "{}", list),
+            Value::Map(map) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ": {}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Writes a string in double quotes, escaping what the literal parser unescapes.
+pub(crate) fn write_string(f: &mut fmt::Formatter<'_>, string: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\t' => write!(f, "\\t")?,
+            '\r' => write!(f, "\\r")?,
+            '\0' => write!(f, "\\0")?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Value::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value.into())
+    }
+}
+
+impl From<Bool> for Value {
+    fn from(value: Bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<char> for Value {
+    fn from(value: char) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Self {
+        Value::Undefined
+    }
+}
+
+/// `None` becomes `undefined`.
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Undefined, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<List<T>> for Value {
+    fn from(list: List<T>) -> Self {
+        let elems: Vec<T> = list.into();
+        Value::from(elems)
+    }
+}
+
+/// The first element ends up at index -1.
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(vec: Vec<T>) -> Self {
+        Value::List(vec.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>, const N: usize> From<[T; N]> for Value {
+    fn from(array: [T; N]) -> Self {
+        Value::List(array.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> From<BTreeMap<K, V>> for Value {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Value::Map(map.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>, S> From<HashMap<K, V, S>> for Value {
+    fn from(map: HashMap<K, V, S>) -> Self {
+        Value::Map(map.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+macro_rules! impl_from_tuple {
+    ($($name:ident),+) => {
+        /// Tuples become lists, the first field ending up at index -1.
+        impl<$($name: Into<Value>),+> From<($($name,)+)> for Value {
+            #[allow(non_snake_case)]
+            fn from(($($name,)+): ($($name,)+)) -> Self {
+                Value::List(List::from([$($name.into()),+]))
+            }
+        }
+    };
+}
+
+impl_from_tuple!(A);
+impl_from_tuple!(A, B);
+impl_from_tuple!(A, B, C);
+impl_from_tuple!(A, B, C, D);
+impl_from_tuple!(A, B, C, D, E);
+impl_from_tuple!(A, B, C, D, E, F);
@@ -0,0 +1,215 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use dreamberd_array::atomic::AtomicList;
+use dreamberd_array::List;
+
+mod common;
+
+const THREADS: usize = 8;
+const PER_THREAD: usize = 20_000;
+
+#[test]
+fn snapshots_index_like_list() {
+    for len in [0, 1, 2, 3, 100] {
+        let list: List<i32> = (0..len).collect();
+        let atomic = AtomicList::from(list.clone());
+        let snapshot = atomic.snapshot();
+        common::assert_gets_like(&list, |index| snapshot.get(index).copied());
+        assert!(snapshot.iter().eq(list.iter()));
+        assert_eq!(snapshot.len(), list.len());
+        assert_eq!(atomic.peek(), list.peek().copied());
+        assert_eq!(format!("{:?}", atomic), format!("{:?}", list));
+        drop(snapshot);
+        assert_eq!(atomic.into_list(), list);
+    }
+}
+
+#[test]
+fn snapshots_outlive_later_writes() {
+    let atomic: AtomicList<String> = (0..3).map(|i| i.to_string()).collect();
+    let before = atomic.snapshot();
+    assert_eq!(atomic.pop().as_deref(), Some("2"));
+    assert_eq!(atomic.pop().as_deref(), Some("1"));
+    atomic.push("x".to_string());
+    let after = atomic.snapshot();
+
+    assert_eq!(
+        List::from(before),
+        List::from(["0", "1", "2"].map(String::from))
+    );
+    assert_eq!(List::from(after), List::from(["0", "x"].map(String::from)));
+    assert_eq!(atomic.pop().as_deref(), Some("x"));
+    assert_eq!(atomic.pop().as_deref(), Some("0"));
+    assert_eq!(atomic.pop(), None);
+    assert!(atomic.snapshot().is_empty());
+}
+
+#[test]
+fn stress_push_pop() {
+    let list = Arc::new(AtomicList::new());
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let workers: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let list = Arc::clone(&list);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                let mut popped = Vec::new();
+                for i in 0..PER_THREAD {
+                    list.push(t * PER_THREAD + i);
+                    if i % 2 == 1 {
+                        popped.extend(list.pop());
+                    }
+                }
+                popped
+            })
+        })
+        .collect();
+    let mut seen = HashSet::new();
+    for worker in workers {
+        for elem in worker.join().unwrap() {
+            assert!(seen.insert(elem), "{} was popped twice", elem);
+        }
+    }
+    while let Some(elem) = list.pop() {
+        assert!(seen.insert(elem), "{} was popped twice", elem);
+    }
+    assert_eq!(seen.len(), THREADS * PER_THREAD);
+}
+
+#[test]
+fn snapshots_under_contention() {
+    let list = Arc::new(AtomicList::new());
+    let writers: Vec<_> = (0..THREADS / 2)
+        .map(|_| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                for i in 0..PER_THREAD {
+                    list.push(vec![i; 4]);
+                    list.pop();
+                }
+            })
+        })
+        .collect();
+    let readers: Vec<_> = (0..THREADS / 2)
+        .map(|_| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                for _ in 0..PER_THREAD / 10 {
+                    let snapshot = list.snapshot();
+                    for elem in snapshot.iter() {
+                        assert!(elem.iter().all(|&x| x == elem[0]));
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in writers.into_iter().chain(readers) {
+        handle.join().unwrap();
+    }
+    assert!(list.is_empty());
+}
+
+#[test]
+fn drops_every_element_once() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let list = Arc::new(AtomicList::new());
+    let workers: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    list.push(Counted);
+                    drop(list.pop());
+                }
+                list.push(Counted);
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    drop(list);
+    // `pop` moves elements out instead of cloning them, so every pushed element is dropped exactly once.
+    assert_eq!(DROPS.load(Ordering::Relaxed), THREADS * 1001);
+}
+
+#[test]
+fn pops_without_clone() {
+    struct Token(Box<usize>);
+
+    let list = Arc::new(AtomicList::new());
+    let workers: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                let mut popped = Vec::new();
+                for i in 0..1000 {
+                    list.push(Token(Box::new(t * 1000 + i)));
+                    popped.extend(list.pop().map(|token| *token.0));
+                }
+                popped
+            })
+        })
+        .collect();
+    let mut seen = HashSet::new();
+    for worker in workers {
+        for elem in worker.join().unwrap() {
+            assert!(seen.insert(elem), "{} was popped twice", elem);
+        }
+    }
+    assert_eq!(seen.len(), THREADS * 1000);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn pop_waits_for_a_clone_in_progress() {
+    struct Slow {
+        cloning: Arc<Barrier>,
+        text: String,
+    }
+
+    impl Clone for Slow {
+        fn clone(&self) -> Self {
+            self.cloning.wait();
+            thread::sleep(Duration::from_millis(50));
+            Slow {
+                cloning: Arc::new(Barrier::new(1)),
+                text: self.text.clone(),
+            }
+        }
+    }
+
+    let cloning = Arc::new(Barrier::new(2));
+    let list = Arc::new(AtomicList::new());
+    list.push(Slow {
+        cloning: Arc::clone(&cloning),
+        text: "slow".to_string(),
+    });
+    let reader = {
+        let list = Arc::clone(&list);
+        thread::spawn(move || list.peek().map(|slow| slow.text))
+    };
+    cloning.wait();
+    let start = Instant::now();
+    let popped = list.pop().map(|slow| slow.text);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert_eq!(popped.as_deref(), Some("slow"));
+    assert_eq!(reader.join().unwrap().as_deref(), Some("slow"));
+    assert!(list.is_empty());
+}
@@ -24,6 +24,7 @@ fn basics() {
 }
 
 #[test]
+#[allow(clippy::option_map_unit_fn)]
 fn peek() {
     let mut list = List::new();
     assert_eq!(list.peek(), None);
@@ -96,6 +97,22 @@ fn get() {
 }
 
 #[test]
+fn get_fractional() {
+    let list: List<i32> = (1..=3).collect();
+
+    assert_eq!(list.get(-0.5), Some(&2));
+    assert_eq!(list.get(-0.0), Some(&2));
+    assert_eq!(list.get(0.25), Some(&3));
+    assert_eq!(list.get(0.75), Some(&3));
+    assert_eq!(list.get(1.5), None);
+    assert_eq!(list.get(-1.5), None);
+    assert_eq!(list.get(f32::NAN), None);
+    assert_eq!(list.get(f32::INFINITY), None);
+    assert_eq!(list.get(1e20), None);
+}
+
+#[test]
+#[allow(clippy::option_map_unit_fn)]
 fn get_mut() {
     let mut list = List::new();
     list.push(1); list.push(2); list.push(3);
@@ -123,3 +140,21 @@ fn insert() {
     assert_eq!(list.get(1.0), Some(&44));
 }
 
+
+#[test]
+fn remove() {
+    let mut list = List::new();
+    list.push(1); list.push(2); list.push(3); list.push(4);
+
+    assert_eq!(list.remove(-2.0), None);
+    assert_eq!(list.remove(2.5), None);
+
+    assert_eq!(list.remove(2.0), Some(4));
+    assert_eq!(list.peek(), Some(&3));
+    assert_eq!(list.remove(-1.0), Some(1));
+    assert_eq!(list.get(-1.0), Some(&2));
+    assert_eq!(list.remove(-0.5), Some(3));
+    assert_eq!(list.remove(-1.0), Some(2));
+    assert_eq!(list.remove(-1.0), None);
+    assert!(list.is_empty());
+}
@@ -0,0 +1,150 @@
+use dreamberd_array::List;
+
+mod common;
+
+use common::Rng;
+
+/// Picks an index in or just around a list of `len` elements, often fractional,
+/// along with the position in a `Vec` holding the list from index -1 up that it must land on,
+/// or `None` if the list must ignore it.
+///
+/// The index is built from the position: position 0 is index -1, and a fraction below
+/// a whole index rounds up to it.
+fn target(rng: &mut Rng, len: usize) -> (f32, Option<usize>) {
+    if rng.next() % 16 == 0 {
+        return (f32::NAN, None);
+    }
+    let pos = (rng.next() % (len as u64 + 2)) as usize;
+    let fraction = [0.0, 0.5, 0.75][rng.next() as usize % 3];
+    let index = pos as f32 - 1.0 - fraction;
+    // Below -1 or past the highest index, there is nothing to land on.
+    let lands = pos < len && (pos > 0 || fraction == 0.0);
+    (index, lands.then_some(pos))
+}
+
+fn assert_same(list: &List<u32>, model: &[u32]) {
+    assert_eq!(list.len(), model.len());
+    assert!(list.iter().eq(model.iter().rev()));
+    assert_eq!(list.clone().len(), model.len());
+    assert_eq!(Vec::from(list.clone()), model);
+}
+
+fn churn(seed: u64, grow: u64) {
+    let mut rng = Rng(seed);
+    let mut list = List::new();
+    let mut model = Vec::new();
+    for elem in 0..20_000 {
+        let (index, pos) = target(&mut rng, model.len());
+        match rng.next() % 10 {
+            n if n < grow => {
+                list.push(elem);
+                model.push(elem);
+            }
+            4 | 5 => {
+                list.insert(index, elem);
+                if let Some(pos) = pos {
+                    model.insert(pos, elem);
+                }
+            }
+            6 => assert_eq!(list.remove(index), pos.map(|pos| model.remove(pos))),
+            7 => assert_eq!(list.pop(), model.pop()),
+            8 => {
+                if let Some(slot) = list.get_mut(index) {
+                    *slot = elem;
+                }
+                if let Some(pos) = pos {
+                    model[pos] = elem;
+                }
+            }
+            _ => assert_eq!(list.get(index), pos.map(|pos| &model[pos])),
+        }
+        if elem % 97 == 0 {
+            assert_same(&list, &model);
+        }
+    }
+    assert_same(&list, &model);
+    for elem in list.iter_mut() {
+        *elem += 1;
+    }
+    assert!(list.into_iter().eq(model.iter().rev().map(|elem| elem + 1)));
+}
+
+#[test]
+fn matches_vec_mostly_pushing() {
+    churn(0x5eed, 4);
+}
+
+#[test]
+fn matches_vec_mostly_inserting() {
+    churn(0xdecade, 2);
+}
+
+#[test]
+fn edge_indexes_across_nodes() {
+    // Enough elements for three nodes, so index -1 and the highest index are in different ones.
+    let model: Vec<u32> = (0..40).collect();
+    let list = List::from(model.clone());
+    let last = model.len() as f32 - 2.0;
+    for (index, pos) in [
+        (-1.0, Some(0)),
+        (-0.25, Some(1)),
+        (-0.0, Some(1)),
+        (last, Some(39)),
+        (last + 0.5, None),
+        (f32::NAN, None),
+    ] {
+        assert_eq!(
+            list.get(index),
+            pos.map(|pos| &model[pos]),
+            "get({})",
+            index
+        );
+
+        let mut removed = list.clone();
+        let mut expected = model.clone();
+        assert_eq!(
+            removed.remove(index),
+            pos.map(|pos| expected.remove(pos)),
+            "remove({})",
+            index
+        );
+        assert_same(&removed, &expected);
+
+        let mut inserted = list.clone();
+        let mut expected = model.clone();
+        inserted.insert(index, 100);
+        if let Some(pos) = pos {
+            expected.insert(pos, 100);
+        }
+        assert_same(&inserted, &expected);
+    }
+}
+
+#[test]
+fn splits_and_merges_nodes() {
+    let mut list: List<u32> = (0..100).collect();
+    let mut model: Vec<u32> = (0..100).collect();
+    for (i, (index, pos)) in [
+        (50.5, 52),
+        (0.0, 1),
+        (-1.0, 0),
+        (98.0, 99),
+        (33.25, 35),
+        (16.0, 17),
+        (15.0, 16),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        list.insert(index, 1000 + i as u32);
+        model.insert(pos, 1000 + i as u32);
+        assert_same(&list, &model);
+    }
+    while model.len() > 3 {
+        // `pos - 1.5` rounds up to index `pos - 1`, which is position `pos`.
+        let pos = model.len() / 3;
+        let index = pos as f32 - 1.5;
+        assert_eq!(list.remove(index), Some(model.remove(pos)));
+        assert_same(&list, &model);
+    }
+}
@@ -0,0 +1,56 @@
+//! Helpers shared by the integration tests that check other lists against [`List`].
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+use dreamberd_array::List;
+
+/// A xorshift generator, so a seed always gives the same numbers.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// The indexes where a list of `len` elements starts, ends, rounds up or gives up.
+pub fn edge_indexes(len: usize) -> Vec<f32> {
+    let last = len as f32 - 2.0;
+    vec![
+        -2.0,
+        -1.5,
+        -1.0,
+        -0.75,
+        -0.0,
+        0.0,
+        0.5,
+        last - 1.0,
+        last - 0.5,
+        last,
+        last + 0.5,
+        last + 1.0,
+        f32::NAN,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+    ]
+}
+
+/// Asserts that `get` finds the same element as [`List::get`] at every edge index of `list`.
+pub fn assert_gets_like<T: Clone + PartialEq + Debug>(
+    list: &List<T>,
+    get: impl Fn(f32) -> Option<T>,
+) {
+    for index in edge_indexes(list.len()) {
+        assert_eq!(
+            get(index),
+            list.get(index).cloned(),
+            "get({}) on {:?}",
+            index,
+            list
+        );
+    }
+}
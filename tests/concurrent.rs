@@ -0,0 +1,327 @@
+#![cfg(feature = "std")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use dreamberd_array::concurrent::ConcurrentList;
+use dreamberd_array::{dlist, List};
+
+mod common;
+
+use common::Rng;
+
+const THREADS: usize = 4;
+const ROUNDS: usize = 300;
+const INDEXES: [f32; 9] = [-1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 2.5, 3.0, 6.0];
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Get(f32),
+    Insert(f32, i32),
+    Remove(f32),
+    Push(i32),
+    Pop,
+}
+
+impl Op {
+    fn run(self, list: &ConcurrentList<i32>) -> Option<i32> {
+        match self {
+            Op::Get(index) => list.get(index),
+            Op::Insert(index, elem) => {
+                list.insert(index, elem);
+                None
+            }
+            Op::Remove(index) => list.remove(index),
+            Op::Push(elem) => {
+                list.push(elem);
+                None
+            }
+            Op::Pop => list.pop(),
+        }
+    }
+
+    fn random(rng: &mut Rng, elem: i32) -> Op {
+        let index = INDEXES[rng.next() as usize % INDEXES.len()];
+        match rng.next() % 8 {
+            0..=2 => Op::Get(index),
+            3 | 4 => Op::Insert(index, elem),
+            5 => Op::Remove(index),
+            6 => Op::Push(elem),
+            _ => Op::Pop,
+        }
+    }
+
+    fn apply(self, list: &mut List<i32>) -> Option<i32> {
+        match self {
+            Op::Get(index) => list.get(index).copied(),
+            Op::Insert(index, elem) => {
+                list.insert(index, elem);
+                None
+            }
+            Op::Remove(index) => list.remove(index),
+            Op::Push(elem) => {
+                list.push(elem);
+                None
+            }
+            Op::Pop => list.pop(),
+        }
+    }
+}
+
+/// One finished call: what it did, what it returned, and when it started and returned.
+#[derive(Clone, Copy, Debug)]
+struct Call {
+    op: Op,
+    result: Option<i32>,
+    invoked: usize,
+    returned: usize,
+}
+
+/// Runs the scripted operations, `THREADS` at a time, one round after the other.
+///
+/// The seed picks every operation and the order the calls of a round start in:
+/// each call waits for its turn before it starts, but not for the calls before it to return.
+/// How the calls overlap after that is up to the OS scheduler, so two runs of a seed
+/// can record different histories, and the checker accepts any that can be linearized.
+fn record(seed: u64, initial: &List<i32>) -> Vec<Vec<Call>> {
+    let mut rng = Rng(seed);
+    let scripts: Vec<Vec<Op>> = (0..THREADS)
+        .map(|t| {
+            (0..ROUNDS)
+                .map(|round| Op::random(&mut rng, (round * THREADS + t) as i32 + 100))
+                .collect()
+        })
+        .collect();
+    // `turns[t][round]` is how many calls of the round start before thread `t`'s.
+    let mut turns = vec![Vec::new(); THREADS];
+    for _ in 0..ROUNDS {
+        let mut order: Vec<usize> = (0..THREADS).collect();
+        for i in (1..THREADS).rev() {
+            order.swap(i, rng.next() as usize % (i + 1));
+        }
+        for (turn, t) in order.into_iter().enumerate() {
+            turns[t].push(turn);
+        }
+    }
+
+    let list = Arc::new(ConcurrentList::from(initial.clone()));
+    let clock = Arc::new(AtomicUsize::new(0));
+    let started = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let workers: Vec<_> = scripts
+        .into_iter()
+        .zip(turns)
+        .map(|(script, turns)| {
+            let list = Arc::clone(&list);
+            let clock = Arc::clone(&clock);
+            let started = Arc::clone(&started);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                script
+                    .into_iter()
+                    .zip(turns)
+                    .enumerate()
+                    .map(|(round, (op, turn))| {
+                        barrier.wait();
+                        while started.load(Ordering::SeqCst) != round * THREADS + turn {
+                            thread::yield_now();
+                        }
+                        let invoked = clock.fetch_add(1, Ordering::SeqCst);
+                        started.fetch_add(1, Ordering::SeqCst);
+                        let result = op.run(&list);
+                        let returned = clock.fetch_add(1, Ordering::SeqCst);
+                        Call {
+                            op,
+                            result,
+                            invoked,
+                            returned,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let calls: Vec<Vec<Call>> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+
+    (0..ROUNDS)
+        .map(|round| calls.iter().map(|thread| thread[round]).collect())
+        .collect()
+}
+
+/// Returns every list a round can leave behind, if its calls can be put in some order
+/// that respects their real-time order and gives the results they returned.
+fn linearize(state: &List<i32>, calls: &[Call], done: &mut Vec<bool>, out: &mut Vec<List<i32>>) {
+    if done.iter().all(|&d| d) {
+        if !out.contains(state) {
+            out.push(state.clone());
+        }
+        return;
+    }
+    for (i, call) in calls.iter().enumerate() {
+        if done[i] {
+            continue;
+        }
+        // A call can only go next if no pending call returned before it started.
+        let blocked = calls
+            .iter()
+            .enumerate()
+            .any(|(j, other)| !done[j] && j != i && other.returned < call.invoked);
+        if blocked {
+            continue;
+        }
+        let mut next = state.clone();
+        if call.op.apply(&mut next) != call.result {
+            continue;
+        }
+        done[i] = true;
+        linearize(&next, calls, done, out);
+        done[i] = false;
+    }
+}
+
+fn check(seed: u64) {
+    let initial = dlist![1, 2, 3, 4, 5, 6];
+    let rounds = record(seed, &initial);
+    let mut states = vec![initial];
+    for (round, calls) in rounds.iter().enumerate() {
+        let mut next = Vec::new();
+        for state in &states {
+            linearize(state, calls, &mut vec![false; calls.len()], &mut next);
+        }
+        assert!(
+            !next.is_empty(),
+            "seed {}: round {} is not linearizable from {:?}: {:#?}",
+            seed,
+            round,
+            states,
+            calls
+        );
+        states = next;
+    }
+}
+
+#[test]
+fn linearizable() {
+    for seed in [1, 7, 42, 1234, 0xdead_beef] {
+        check(seed);
+    }
+}
+
+#[test]
+fn checker_rejects_wrong_results() {
+    let calls = [
+        Call {
+            op: Op::Remove(-1.0),
+            result: Some(1),
+            invoked: 0,
+            returned: 1,
+        },
+        Call {
+            op: Op::Get(-1.0),
+            result: Some(1),
+            invoked: 2,
+            returned: 3,
+        },
+    ];
+    let mut out = Vec::new();
+    linearize(&dlist![1, 2], &calls, &mut vec![false; 2], &mut out);
+    assert!(out.is_empty());
+
+    // Overlapping, the get may have happened first.
+    let calls = [
+        Call {
+            invoked: 0,
+            returned: 3,
+            ..calls[0]
+        },
+        Call {
+            invoked: 1,
+            returned: 2,
+            ..calls[1]
+        },
+    ];
+    linearize(&dlist![1, 2], &calls, &mut vec![false; 2], &mut out);
+    assert_eq!(out, vec![dlist![2]]);
+}
+
+#[test]
+fn gets_like_list() {
+    for len in [0, 1, 2, 3, 100] {
+        let list: List<i32> = (0..len).collect();
+        let concurrent = ConcurrentList::from(list.clone());
+        common::assert_gets_like(&list, |index| concurrent.get(index));
+        assert_eq!(format!("{:?}", concurrent), format!("{:?}", list));
+    }
+}
+
+#[test]
+fn edits_at_every_distance_from_the_head() {
+    for len in 0..5 {
+        let list: List<i32> = (0..len).collect();
+        for index in common::edge_indexes(list.len()) {
+            let mut expected = list.clone();
+            let concurrent = ConcurrentList::from(list.clone());
+            expected.insert(index, 99);
+            concurrent.insert(index, 99);
+            assert_eq!(concurrent.len(), expected.len());
+            assert_eq!(
+                concurrent.into_list(),
+                expected,
+                "insert({}) into {:?}",
+                index,
+                list
+            );
+
+            let mut expected = list.clone();
+            let concurrent = ConcurrentList::from(list.clone());
+            assert_eq!(
+                concurrent.remove(index),
+                expected.remove(index),
+                "remove({}) from {:?}",
+                index,
+                list
+            );
+            assert_eq!(concurrent.len(), expected.len());
+            assert_eq!(concurrent.into_list(), expected);
+        }
+    }
+}
+
+#[test]
+fn empty() {
+    let list: ConcurrentList<i32> = ConcurrentList::default();
+    list.insert(-1.0, 1);
+    assert!(list.is_empty());
+    assert_eq!(list.get(-1.0), None);
+    assert_eq!(list.remove(-1.0), None);
+    assert_eq!(list.pop(), None);
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn many_writers() {
+    let list: Arc<ConcurrentList<usize>> = Arc::new((0..100).collect());
+    let workers: Vec<_> = (0..8)
+        .map(|t| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                for i in 0..500 {
+                    list.insert((t * 10) as f32 + 0.5, i);
+                    assert!(list.remove((t * 10) as f32 + 0.5).is_some());
+                    list.get(50.0);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert_eq!(list.len(), 100);
+}
+
+#[test]
+fn long_list_drops() {
+    let list: ConcurrentList<u32> = (0..1_000_000).collect();
+    drop(list);
+}
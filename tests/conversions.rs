@@ -0,0 +1,74 @@
+use std::collections::VecDeque;
+
+use dreamberd_array::List;
+
+#[test]
+fn from_iter() {
+    let list: List<usize> = vec![1, 2, 3].into_iter().collect();
+    assert_eq!(list[-1.0], 1);
+    assert_eq!(list[0.0], 2);
+    assert_eq!(list[1.0], 3);
+
+    let mut manual = List::new();
+    for i in [1, 2, 3] {
+        manual.push(i);
+    }
+    assert_eq!(list, manual);
+}
+
+#[test]
+fn extend() {
+    let mut list = List::from([1]);
+    list.extend(vec![2, 3]);
+    list.extend(&[4]);
+    assert_eq!(list.len(), 4);
+    assert_eq!(list[-1.0], 1);
+    assert_eq!(list[2.0], 4);
+}
+
+#[test]
+fn from_vec_and_array() {
+    let from_vec = List::from(vec!['a', 'b', 'c']);
+    let from_array = List::from(['a', 'b', 'c']);
+    assert_eq!(from_vec, from_array);
+    assert_eq!(from_vec[-1.0], 'a');
+
+    let empty: List<usize> = List::from(Vec::new());
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn into_vec_round_trip() {
+    let input = vec![vec![1, 2], vec![3], vec![]];
+    let list = List::from(input.clone());
+    let output: Vec<Vec<usize>> = list.into();
+    assert_eq!(output, input);
+}
+
+#[test]
+fn into_vec_deque() {
+    let list = List::from([1, 2, 3]);
+    let deque: VecDeque<usize> = list.into();
+    assert_eq!(deque, VecDeque::from(vec![1, 2, 3]));
+}
+
+#[test]
+fn for_loops() {
+    let mut list = List::from([1, 2, 3]);
+
+    for entry in &mut list {
+        *entry *= 10;
+    }
+
+    let mut seen = Vec::new();
+    for entry in &list {
+        seen.push(*entry);
+    }
+    assert_eq!(seen, vec![30, 20, 10]);
+
+    let mut owned = Vec::new();
+    for entry in list {
+        owned.push(entry);
+    }
+    assert_eq!(owned, vec![30, 20, 10]);
+}
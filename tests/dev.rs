@@ -1,6 +1,7 @@
 use dreamberd_array::List;
 
 #[test]
+#[allow(clippy::useless_vec)]
 fn indexing() {
     let input: Vec<usize> = vec![1, 2, 3];
     let mut list = List::new();
@@ -0,0 +1,201 @@
+use dreamberd_array::encode::{self, Decode, DecodeError, Encode};
+use dreamberd_array::{dlist, List};
+
+#[test]
+fn primitives() {
+    let list = dlist![(1u8, -2i16, 3u32, -4i64), (u8::MAX, i16::MIN, u32::MAX, i64::MIN)];
+    let back: List<(u8, i16, u32, i64)> = encode::from_bytes(&encode::to_bytes(&list)).unwrap();
+    assert_eq!(back, list);
+
+    let list = dlist![(1.5f32, -2.25f64, true, 'ß', 7usize, -7isize)];
+    let back: List<(f32, f64, bool, char, usize, isize)> =
+        encode::from_bytes(&encode::to_bytes(&list)).unwrap();
+    assert_eq!(back, list);
+}
+
+#[test]
+fn compounds() {
+    let list = dlist![vec!["a".to_string()], vec![], vec!["b".to_string(), "c".to_string()]];
+    let back: List<Vec<String>> = encode::from_bytes(&encode::to_bytes(&list)).unwrap();
+    assert_eq!(back, list);
+}
+
+#[test]
+fn nested() {
+    let list = dlist![dlist![1u16, 2], dlist![], dlist![3; 0.5 => 4]];
+    let bytes = encode::to_bytes(&list);
+    let back: List<List<u16>> = encode::from_bytes(&bytes).unwrap();
+    assert_eq!(back, list);
+    assert_eq!(back[1.0][-1.0], 3);
+}
+
+#[test]
+fn empty() {
+    let list: List<u8> = List::new();
+    let back: List<u8> = encode::from_bytes(&encode::to_bytes(&list)).unwrap();
+    assert!(back.is_empty());
+}
+
+#[test]
+fn keys() {
+    let list = dlist![1u8, 2, 3];
+    let plain = encode::to_bytes(&list);
+    let keyed = encode::to_bytes_with_keys(&list);
+    assert_eq!(keyed.len(), plain.len() + 3 * 4);
+
+    let back: List<u8> = encode::from_bytes(&keyed).unwrap();
+    assert_eq!(back, list);
+}
+
+#[test]
+fn fractional_keys() {
+    // Hand written body: three elements at 1.0, -1.0 and 0.5.
+    let mut body = Vec::new();
+    3u64.encode(&mut body);
+    for (key, elem) in [(1.0f32, 'c'), (-1.0, 'a'), (0.5, 'b')] {
+        key.encode(&mut body);
+        elem.encode(&mut body);
+    }
+    let bytes = frame(1, &body);
+
+    let back: List<char> = encode::from_bytes(&bytes).unwrap();
+    assert_eq!(back, dlist!['a', 'b', 'c']);
+}
+
+#[test]
+fn invalid_keys() {
+    let mut body = Vec::new();
+    2u64.encode(&mut body);
+    for key in [0.0f32, 0.0] {
+        key.encode(&mut body);
+        1u8.encode(&mut body);
+    }
+    assert_eq!(encode::from_bytes::<u8>(&frame(1, &body)), Err(DecodeError::InvalidKey(0.0)));
+
+    let mut body = Vec::new();
+    1u64.encode(&mut body);
+    f32::INFINITY.encode(&mut body);
+    1u8.encode(&mut body);
+    assert_eq!(
+        encode::from_bytes::<u8>(&frame(1, &body)),
+        Err(DecodeError::InvalidKey(f32::INFINITY))
+    );
+}
+
+#[test]
+fn truncated() {
+    let bytes = encode::to_bytes(&dlist!["hello".to_string(), "world".to_string()]);
+    for len in 0..bytes.len() {
+        assert_eq!(
+            encode::from_bytes::<String>(&bytes[..len]),
+            Err(DecodeError::UnexpectedEnd),
+            "length {}",
+            len
+        );
+    }
+}
+
+#[test]
+fn corrupted() {
+    let bytes = encode::to_bytes(&dlist![1u32, 2, 3]);
+
+    let mut bad = bytes.clone();
+    bad[0] = b'X';
+    assert_eq!(encode::from_bytes::<u32>(&bad), Err(DecodeError::BadMagic));
+
+    let mut bad = bytes.clone();
+    bad[4] = 9;
+    assert_eq!(encode::from_bytes::<u32>(&bad), Err(DecodeError::UnsupportedVersion(9)));
+
+    let mut bad = bytes.clone();
+    bad[5] = 0x80;
+    assert_eq!(encode::from_bytes::<u32>(&bad), Err(DecodeError::UnknownFlags(0x80)));
+
+    for i in 14..bytes.len() {
+        let mut bad = bytes.clone();
+        bad[i] ^= 0x01;
+        assert_eq!(encode::from_bytes::<u32>(&bad), Err(DecodeError::ChecksumMismatch));
+    }
+
+    let mut bad = bytes.clone();
+    bad.push(0);
+    assert_eq!(encode::from_bytes::<u32>(&bad), Err(DecodeError::TrailingBytes(1)));
+}
+
+#[test]
+fn invalid_values() {
+    let mut body = Vec::new();
+    1u64.encode(&mut body);
+    2u8.encode(&mut body);
+    assert_eq!(encode::from_bytes::<bool>(&frame(0, &body)), Err(DecodeError::InvalidBool(2)));
+
+    let mut body = Vec::new();
+    1u64.encode(&mut body);
+    0xD800u32.encode(&mut body);
+    assert_eq!(encode::from_bytes::<char>(&frame(0, &body)), Err(DecodeError::InvalidChar(0xD800)));
+
+    let mut body = Vec::new();
+    1u64.encode(&mut body);
+    vec![0xFFu8].encode(&mut body);
+    assert_eq!(encode::from_bytes::<String>(&frame(0, &body)), Err(DecodeError::InvalidUtf8));
+
+    // A huge element count must fail cleanly instead of allocating.
+    let mut body = Vec::new();
+    u64::MAX.encode(&mut body);
+    assert!(encode::from_bytes::<u8>(&frame(0, &body)).is_err());
+
+    // Elements that do not consume the whole body.
+    let mut body = Vec::new();
+    1u64.encode(&mut body);
+    1u16.encode(&mut body);
+    assert_eq!(encode::from_bytes::<u8>(&frame(0, &body)), Err(DecodeError::TrailingBytes(1)));
+}
+
+#[test]
+fn custom_types() {
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Encode for Point {
+        fn encode(&self, out: &mut Vec<u8>) {
+            (self.x, self.y).encode(out);
+        }
+    }
+
+    impl Decode for Point {
+        fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+            let (x, y) = <(i32, i32)>::decode(input)?;
+            Ok(Point { x, y })
+        }
+    }
+
+    let list = dlist![Point { x: 1, y: 2 }, Point { x: -3, y: 4 }];
+    let back: List<Point> = encode::from_bytes(&encode::to_bytes(&list)).unwrap();
+    assert_eq!(back, list);
+}
+
+#[test]
+fn error_display() {
+    assert_eq!(DecodeError::UnexpectedEnd.to_string(), "unexpected end of input");
+    assert_eq!(DecodeError::UnknownFlags(2).to_string(), "unknown flags 0x02");
+}
+
+/// Wraps a body in a valid header and checksum.
+fn frame(flags: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = b"DBAR".to_vec();
+    out.push(1);
+    out.push(flags);
+    (body.len() as u64).encode(&mut out);
+    out.extend_from_slice(body);
+
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in body {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    out.extend_from_slice(&hash.to_le_bytes());
+    out
+}
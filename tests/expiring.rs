@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+
+use std::time::Duration;
+
+use dreamberd_array::dlist;
+use dreamberd_array::expiring::{Clock, ExpiringList, Lifetime, ManualClock, SystemClock};
+
+fn list() -> (ExpiringList<&'static str, ManualClock>, ManualClock) {
+    let clock = ManualClock::new();
+    (ExpiringList::with_clock(clock.clone()), clock)
+}
+
+#[test]
+fn ops_lifetime() {
+    let (mut list, _) = list();
+    list.push_with_lifetime("a", Lifetime::Ops(3));
+    list.push("b");
+
+    assert_eq!(list.get(-1.0), Some(&"a"));
+    assert_eq!(list.get(0.0), Some(&"b"));
+    assert_eq!(list.get(-1.0), Some(&"b"));
+    assert_eq!(list.len(), 1);
+}
+
+#[test]
+fn duration_lifetime() {
+    let (mut list, clock) = list();
+    list.push_with_lifetime("short", Lifetime::Duration(Duration::from_secs(2)));
+    clock.advance(Duration::from_secs(1));
+    list.push_with_lifetime("long", Lifetime::Duration(Duration::from_secs(20)));
+    list.push_with_lifetime("forever", Lifetime::Infinity);
+
+    assert_eq!(list.len(), 3);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec!["forever", "long"]);
+    clock.set(Duration::from_secs(60));
+    assert_eq!(list.pop(), Some("forever"));
+    assert!(list.is_empty());
+}
+
+#[test]
+fn expiry_in_the_middle() {
+    let (mut list, _) = list();
+    list.push("a");
+    list.push("b");
+    list.insert_with_lifetime(-0.5, "x", Lifetime::Ops(1));
+
+    assert_eq!(format!("{:?}", list), "[\"a\", \"x\", \"b\"]");
+    assert_eq!(list.get(0.0), Some(&"x"));
+    assert_eq!(list.get(0.0), Some(&"b"));
+    assert_eq!(format!("{:?}", list), "[\"a\", \"b\"]");
+}
+
+#[test]
+fn mutate_and_remove() {
+    let (mut list, _) = list();
+    list.push_with_lifetime("a", Lifetime::Ops(10));
+    list.push("b");
+    *list.get_mut(-1.0).unwrap() = "c";
+    list.insert(-0.5, "d");
+
+    assert_eq!(list.remove(0.0), Some("d"));
+    assert_eq!(list.into_list(), dlist!["c", "b"]);
+}
+
+#[test]
+fn system_clock() {
+    let clock = SystemClock::new();
+    assert!(clock.now() < Duration::from_secs(60));
+
+    let mut list = ExpiringList::new();
+    list.push_with_lifetime(1, Lifetime::Duration(Duration::from_secs(3600)));
+    list.push_with_lifetime(2, Lifetime::Duration(Duration::ZERO));
+    assert_eq!(list.into_list(), dlist![1]);
+}
@@ -0,0 +1,128 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dreamberd_array::fixed::{CapacityError, FixedList};
+use dreamberd_array::{dlist, List};
+
+mod common;
+
+use common::Rng;
+
+fn assert_same(fixed: &FixedList<i32, 8>, list: &List<i32>) {
+    assert_eq!(fixed.len(), list.len());
+    assert!(fixed.iter().eq(list.iter()));
+    assert_eq!(format!("{:?}", fixed), format!("{:?}", list));
+    common::assert_gets_like(list, |index| fixed.get(index).copied());
+}
+
+#[test]
+fn matches_list() {
+    let mut rng = Rng(0x1234_5678);
+    let mut fixed: FixedList<i32, 8> = FixedList::new();
+    let mut list = List::new();
+    for elem in 0..5_000 {
+        let indexes = common::edge_indexes(list.len());
+        let index = indexes[rng.next() as usize % indexes.len()];
+        match rng.next() % 5 {
+            0 => {
+                if fixed.push(elem).is_ok() {
+                    list.push(elem);
+                } else {
+                    assert_eq!(list.len(), 8);
+                }
+            }
+            1 => assert_eq!(fixed.pop(), list.pop()),
+            2 => assert_eq!(fixed.remove(index), list.remove(index)),
+            3 => {
+                if let Some(slot) = fixed.get_mut(index) {
+                    *slot = elem;
+                }
+                if let Some(slot) = list.get_mut(index) {
+                    *slot = elem;
+                }
+            }
+            _ => match fixed.insert(index, elem) {
+                Ok(()) => list.insert(index, elem),
+                Err(CapacityError(back)) => {
+                    assert_eq!(back, elem);
+                    assert!(fixed.is_full());
+                }
+            },
+        }
+        assert_same(&fixed, &list);
+    }
+}
+
+#[test]
+fn full() {
+    let mut list: FixedList<&str, 2> = FixedList::new();
+    assert_eq!(list.push("a"), Ok(()));
+    assert_eq!(list.push("b"), Ok(()));
+    assert!(list.is_full());
+    assert_eq!(list.push("c"), Err(CapacityError("c")));
+    assert_eq!(list.insert(-1.0, "c"), Err(CapacityError("c")));
+    assert_eq!(CapacityError("c").to_string(), "the list is full");
+    assert_eq!(list.peek(), Some(&"b"));
+
+    // Out of range indexes are ignored before the capacity is checked, like `List::insert`.
+    assert_eq!(list.insert(5.0, "c"), Ok(()));
+    assert_eq!(list.len(), 2);
+}
+
+#[test]
+fn empty() {
+    let mut list: FixedList<i32, 0> = FixedList::new();
+    assert_eq!(list.push(1), Err(CapacityError(1)));
+    assert_eq!(list.insert(-1.0, 1), Ok(()));
+    assert_eq!(list.pop(), None);
+    assert_eq!(list.remove(-1.0), None);
+    assert_eq!(list.get(-1.0), None);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn iterators() {
+    let mut list: FixedList<i32, 4> = [1, 2, 3].into_iter().collect();
+    for elem in &mut list {
+        *elem *= 10;
+    }
+    list[-1.0] += 1;
+    assert_eq!((&list).into_iter().collect::<Vec<_>>(), vec![&30, &20, &11]);
+    assert_eq!(
+        list.clone().into_iter().collect::<Vec<_>>(),
+        vec![30, 20, 11]
+    );
+    assert_eq!(List::from(list), dlist![11, 20, 30]);
+}
+
+#[test]
+#[should_panic]
+fn collect_too_many() {
+    let _: FixedList<i32, 2> = (0..3).collect();
+}
+
+#[test]
+fn drops_every_element_once() {
+    let drops = Rc::new(Cell::new(0));
+    struct Counted(Rc<Cell<usize>>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let mut list: FixedList<Counted, 4> = FixedList::new();
+    for _ in 0..4 {
+        assert!(list.push(Counted(Rc::clone(&drops))).is_ok());
+    }
+    drop(list.remove(0.0));
+    drop(list.pop());
+    assert_eq!(drops.get(), 2);
+    assert!(list.insert(-1.0, Counted(Rc::clone(&drops))).is_ok());
+
+    let mut iter = list.into_iter();
+    drop(iter.next());
+    assert_eq!(drops.get(), 3);
+    drop(iter);
+    assert_eq!(drops.get(), 5);
+}
@@ -0,0 +1,49 @@
+use dreamberd_array::List;
+
+#[test]
+fn debug() {
+    let mut list = List::new();
+    assert_eq!(format!("{:?}", list), "[]");
+
+    list.push(1); list.push(2); list.push(3);
+    assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+
+    list.insert(-0.5, 42);
+    assert_eq!(format!("{:?}", list), "[1, 42, 2, 3]");
+}
+
+#[test]
+fn debug_nested() {
+    let list = List::from(vec![List::from(vec!["a"]), List::new()]);
+    assert_eq!(format!("{:?}", list), "[[\"a\"], []]");
+    assert_eq!(format!("{:#?}", List::from([1])), "[\n    1,\n]");
+}
+
+#[test]
+fn debug_long() {
+    let list: List<usize> = (0..1_000_000).collect();
+    let out = format!("{:?}", list);
+    assert!(out.starts_with("[0, 1, 2"));
+    assert!(out.ends_with("999999]"));
+}
+
+#[test]
+fn display() {
+    let list = List::from(vec![1.5, 2.0, 3.25]);
+    assert_eq!(format!("{}", list), "[1.5, 2, 3.25]");
+    assert_eq!(list.to_string(), "[1.5, 2, 3.25]");
+
+    let empty: List<usize> = List::new();
+    assert_eq!(format!("{}", empty), "[]");
+    assert_eq!(format!("{:#}", empty), "");
+}
+
+#[test]
+fn display_indexed() {
+    let mut list = List::from(vec!['a', 'b', 'c']);
+    assert_eq!(format!("{:#}", list), "-1: a\n0: b\n1: c");
+
+    list.insert(0.5, 'x');
+    assert_eq!(list[0.5], 'x');
+    assert_eq!(format!("{:#}", list), "-1: a\n0: b\n1: x\n2: c");
+}
@@ -0,0 +1,141 @@
+use dreamberd_array::dlist;
+use dreamberd_array::history::HistoryList;
+
+fn history(list: &HistoryList<char>, index: f32) -> String {
+    list.history(index).unwrap().collect()
+}
+
+#[test]
+fn overwrites() {
+    let mut list = HistoryList::from(dlist!['a', 'b']);
+    list[-1.0] = 'c';
+    *list.get_mut(-1.0).unwrap() = 'd';
+    list[0.0] = 'e';
+
+    assert_eq!(history(&list, -1.0), "ca");
+    assert_eq!(history(&list, 0.0), "b");
+    assert_eq!(list.previous(-1.0, 2), Some(&'a'));
+    assert_eq!(list.previous(-1.0, 3), None);
+    assert_eq!(list.previous(1.0, 0), None);
+}
+
+#[test]
+fn inserts_shift_history() {
+    let mut list = HistoryList::from(dlist!['a', 'b', 'c']);
+    list.insert(-0.5, 'x');
+
+    assert_eq!(list.as_list(), &dlist!['a', 'x', 'b', 'c']);
+    assert_eq!(history(&list, -1.0), "");
+    assert_eq!(history(&list, 0.0), "b");
+    assert_eq!(history(&list, 1.0), "c");
+    assert_eq!(history(&list, 2.0), "");
+
+    list.insert(5.0, 'z');
+    assert_eq!(list.len(), 4);
+}
+
+#[test]
+fn removes_shift_history() {
+    let mut list = HistoryList::from(dlist!['a', 'b', 'c']);
+
+    assert_eq!(list.remove(0.0), Some('b'));
+    assert_eq!(list.as_list(), &dlist!['a', 'c']);
+    assert_eq!(history(&list, -1.0), "");
+    assert_eq!(history(&list, 0.0), "b");
+    assert_eq!(list.remove(3.0), None);
+}
+
+#[test]
+fn push_and_pop() {
+    let mut list = HistoryList::new();
+    list.push('a');
+    list[-1.0] = 'b';
+    assert_eq!(list.pop(), Some('b'));
+    list.push('c');
+
+    assert_eq!(history(&list, -1.0), "");
+}
+
+#[test]
+fn retention() {
+    let mut list = HistoryList::with_retention(2);
+    list.push('a');
+    for c in ['b', 'c', 'd'] {
+        list[-1.0] = c;
+    }
+    assert_eq!(history(&list, -1.0), "cb");
+
+    list.set_retention(None);
+    list[-1.0] = 'e';
+    assert_eq!(history(&list, -1.0), "dcb");
+
+    list.set_retention(Some(0));
+    list[-1.0] = 'f';
+    assert_eq!(history(&list, -1.0), "");
+
+    list.set_retention(Some(5));
+    list[-1.0] = 'g';
+    list.clear_history();
+    assert_eq!(history(&list, -1.0), "");
+    assert_eq!(list.into_list(), dlist!['g']);
+}
+
+#[test]
+fn matches_copying_every_shift() {
+    // Keeps every slot's old values directly, copying them on every shift.
+    let mut model: Vec<(char, Vec<char>)> = Vec::new();
+    let mut list = HistoryList::new();
+    let mut seed = 0x2545_f491_u32;
+    for step in 0..2_000u32 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let elem = char::from(b'a' + (step % 26) as u8);
+        let position = seed as usize % (model.len() + 1);
+        let index = position as f32 - 1.0;
+        match seed % 5 {
+            0 => {
+                list.push(elem);
+                model.push((elem, Vec::new()));
+            }
+            1 => assert_eq!(list.pop(), model.pop().map(|(value, _)| value)),
+            2 if position < model.len() => {
+                list[index] = elem;
+                let (value, old) = &mut model[position];
+                old.insert(0, *value);
+                *value = elem;
+            }
+            3 if position < model.len() => {
+                list.insert(index, elem);
+                for (value, old) in &mut model[position..] {
+                    old.insert(0, *value);
+                }
+                let values: Vec<char> = model.iter().map(|(value, _)| *value).collect();
+                model.push((*values.last().unwrap(), Vec::new()));
+                model[position].0 = elem;
+                for (slot, value) in model[position + 1..].iter_mut().zip(&values[position..]) {
+                    slot.0 = *value;
+                }
+            }
+            4 if position < model.len() => {
+                assert_eq!(list.remove(index), Some(model[position].0));
+                for (value, old) in &mut model[position..] {
+                    old.insert(0, *value);
+                }
+                let values: Vec<char> = model.iter().map(|(value, _)| *value).collect();
+                model.pop();
+                for (slot, value) in model[position..].iter_mut().zip(&values[position + 1..]) {
+                    slot.0 = *value;
+                }
+            }
+            _ => {}
+        }
+        if step % 50 == 0 {
+            for (position, (value, old)) in model.iter().enumerate() {
+                let index = position as f32 - 1.0;
+                assert_eq!(list[index], *value);
+                assert_eq!(history(&list, index), old.iter().collect::<String>());
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "std")]
+
+use std::fs;
+use std::path::Path;
+
+use dreamberd_array::lang::{self, Interpreter, LangErrorKind};
+use dreamberd_array::Value;
+
+/// Runs a script and returns what it printed, followed by an `error:` line if it failed.
+fn transcript(src: &str) -> String {
+    let mut interpreter = Interpreter::new();
+    let result = interpreter.run(src);
+    let mut out = interpreter.take_output();
+    if let Err(err) = result {
+        out.push_str(&format!("error: {}\n", err));
+    }
+    out
+}
+
+#[test]
+fn golden() {
+    let mut scripts: Vec<_> = fs::read_dir("tests/lang")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        .collect();
+    scripts.sort();
+    assert!(!scripts.is_empty());
+    for script in scripts {
+        let src = fs::read_to_string(&script).unwrap();
+        let expected = fs::read_to_string(script.with_extension("out"))
+            .unwrap_or_else(|_| panic!("missing expected output for {}", script.display()));
+        assert_eq!(transcript(&src), expected, "{}", script.display());
+    }
+}
+
+#[test]
+fn golden_files_are_paired() {
+    for entry in fs::read_dir("tests/lang").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "out") {
+            assert!(Path::new(&path.with_extension("db")).exists(), "{}", path.display());
+        }
+    }
+}
+
+#[test]
+fn variables_persist_between_runs() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run("var var n = 1!").unwrap();
+    interpreter.run("n = n + 1!").unwrap();
+    assert_eq!(interpreter.get("n"), Some(&Value::from(2)));
+    assert_eq!(interpreter.get("m"), None);
+}
+
+#[test]
+fn errors() {
+    let kind = |src| lang::run(src).unwrap_err().kind;
+
+    assert_eq!(kind("print(x)!"), LangErrorKind::UndefinedVariable("x".to_string()));
+    assert_eq!(kind("const var x = 1!\nx = 2!"), LangErrorKind::Reassign("x".to_string()));
+    assert_eq!(kind("var const xs = [1]!\npush(xs, 2)!"), LangErrorKind::Mutate("xs".to_string()));
+    assert_eq!(kind("foo()!"), LangErrorKind::UnknownFunction("foo".to_string()));
+    assert_eq!(kind("1 = 2!"), LangErrorKind::InvalidAssignment);
+    assert_eq!(kind("print(1)"), LangErrorKind::UnexpectedEnd);
+    assert_eq!(kind("print(@)!"), LangErrorKind::UnexpectedChar('@'));
+    assert_eq!(kind("'abc"), LangErrorKind::UnterminatedString);
+    assert!(matches!(kind("[1] - 1!"), LangErrorKind::Type(_)));
+    assert!(matches!(kind("len()!"), LangErrorKind::Arity { expected: 1, found: 0, .. }));
+    assert_eq!(kind("print(1e999)!"), LangErrorKind::InvalidNumber("1e999".to_string()));
+    assert_eq!(kind("print('\\q')!"), LangErrorKind::InvalidEscape('q'));
+    assert!(matches!(kind("const var xs = [1]!\nprint(xs[5][0])!"), LangErrorKind::Type(_)));
+
+    let err = lang::run("const var xs = [1]!\nprint(xs['a'])!").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 10));
+    assert_eq!(err.to_string(), "2:10: cannot index with a string");
+}
+
+#[test]
+fn nesting_too_deep() {
+    let nested = |open: &str, inner: &str, close: &str, levels: usize| {
+        format!(
+            "const const a = {}{}{}!",
+            open.repeat(levels),
+            inner,
+            close.repeat(levels)
+        )
+    };
+    let deep = [
+        nested("[", "1", "]", 5_000),
+        nested("(", "1", ")", 5_000),
+        nested("-", "1", "", 5_000),
+        nested("", "1", "+1", 5_000),
+        nested("", "1", "*2", 5_000),
+        nested("", "[1]", "[0]", 5_000),
+        nested("len([", "1", "])", 5_000),
+    ];
+    for src in &deep {
+        let err = lang::run(src).unwrap_err();
+        assert_eq!(err.kind, LangErrorKind::NestingTooDeep, "{:.40}", src);
+        assert_eq!(err.line, 1);
+        assert!(err.column > 100, "{:.40}", src);
+    }
+    let err = lang::run(&nested("[", "1", "]", 200)).unwrap_err();
+    assert_eq!(err.to_string(), "1:145: expression nested too deeply");
+
+    // Shallower nesting still runs.
+    assert_eq!(lang::run(&(nested("[", "", "]", 120) + "\nprint(len(a))!")).unwrap(), "1\n");
+    assert_eq!(lang::run(&(nested("(", "1", "+1)", 100) + "\nprint(a)!")).unwrap(), "101\n");
+    assert_eq!(lang::run(&(nested("-", "1", "", 121) + "\nprint(a)!")).unwrap(), "-1\n");
+}
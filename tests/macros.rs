@@ -0,0 +1,58 @@
+use dreamberd_array::{dlist, List};
+
+#[test]
+fn empty() {
+    let list: List<usize> = dlist![];
+    assert!(list.is_empty());
+}
+
+#[test]
+fn elements() {
+    let list = dlist![1, 2, 3];
+    assert_eq!(list[-1.0], 1);
+    assert_eq!(list[0.0], 2);
+    assert_eq!(list[1.0], 3);
+    assert_eq!(list, List::from(vec![1, 2, 3]));
+
+    let trailing = dlist!["a", "b",];
+    assert_eq!(trailing.len(), 2);
+}
+
+#[test]
+fn matches_push_loop() {
+    let mut list = List::new();
+    for i in [1, 2, 3] {
+        list.push(i);
+    }
+    assert_eq!(dlist![1, 2, 3], list);
+}
+
+#[test]
+fn insertions() {
+    let list = dlist![1, 2, 3; -0.5 => 42, 1.5 => 69];
+
+    let mut manual = List::from([1, 2, 3]);
+    manual.insert(-0.5, 42);
+    manual.insert(1.5, 69);
+
+    assert_eq!(list, manual);
+    assert_eq!(list[-0.5], 42);
+    assert_eq!(list[1.5], 69);
+    assert_eq!(format!("{:?}", list), "[1, 42, 2, 69, 3]");
+}
+
+#[test]
+fn insertions_into_empty() {
+    let list: List<usize> = dlist![; 0.5 => 42];
+    assert!(list.is_empty());
+}
+
+#[test]
+fn expressions() {
+    let base = 10;
+    let offset = 0.5;
+    let list = dlist![base + 1, base * 2; offset - 1.0 => base];
+    assert_eq!(list[-1.0], 11);
+    assert_eq!(list[-0.5], 10);
+    assert_eq!(list[1.0], 20);
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use dreamberd_array::mutability::Binding;
+use dreamberd_array::{dlist, ConstConst, ConstVar, List, VarConst, VarVar};
+
+#[test]
+fn reading() {
+    let list = ConstConst::new(dlist![1, 2, 3]);
+
+    assert_eq!(list.len(), 3);
+    assert_eq!(list[0.5], 3);
+    assert_eq!(list.get(-1.0), Some(&1));
+    assert_eq!(list.peek(), Some(&3));
+    assert_eq!((&list).into_iter().count(), 3);
+    assert_eq!(list.to_string(), "[1, 2, 3]");
+    assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+}
+
+#[test]
+fn changing_contents() {
+    let mut list: ConstVar<i32> = List::new().into();
+    list.push(1);
+    list.push(2);
+    list.insert(-0.5, 42);
+    list[1.0] = 3;
+    *list.get_mut(-1.0).unwrap() += 10;
+    list.extend([4]);
+    for value in &mut list {
+        *value *= 2;
+    }
+
+    assert_eq!(list.remove(0.0), Some(84));
+    assert_eq!(list.pop(), Some(8));
+    assert_eq!(Vec::from(List::from(list)), vec![22, 6]);
+}
+
+#[test]
+fn reassigning() {
+    let mut list = VarConst::new(dlist![1]);
+    let old = list.reassign(dlist![2, 3]);
+
+    assert_eq!(old, dlist![1]);
+    assert_eq!(list.as_list(), &dlist![2, 3]);
+
+    let mut list = VarVar::new(dlist![1]);
+    list.reassign(dlist![2]);
+    list.push(3);
+    assert_eq!(list.into_inner(), dlist![2, 3]);
+}
+
+#[test]
+fn tightening() {
+    let list = VarVar::new(dlist![1, 2]);
+    let list: VarConst<i32> = list.freeze();
+    let list: ConstConst<i32> = list.fix();
+    assert_eq!(list.as_list(), &dlist![1, 2]);
+
+    let list = VarVar::new(dlist![1, 2]);
+    let mut list: ConstVar<i32> = list.fix();
+    list.push(3);
+    let list: ConstConst<i32> = list.freeze();
+    assert_eq!(list.len(), 3);
+}
+
+#[test]
+fn traits() {
+    let a: ConstConst<i32> = (1..=3).collect();
+    let b = a.clone();
+    assert_eq!(a, b);
+
+    let set: HashSet<ConstConst<i32>> = [a, b].into_iter().collect();
+    assert_eq!(set.len(), 1);
+
+    let empty: VarVar<i32> = Binding::default();
+    assert!(empty.is_empty());
+
+    let list = ConstConst::new(dlist![1, 2]);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![2, 1]);
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "parallel")]
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dreamberd_array::{dlist, List};
+
+fn big() -> List<u64> {
+    (0..10_000u64)
+        .map(|i| i.wrapping_mul(2_654_435_761) % 1000)
+        .collect()
+}
+
+#[test]
+fn map_matches_iter_order() {
+    let list = big();
+    let sequential: Vec<u64> = list.iter().map(|x| x * 3).collect();
+    for threads in [1, 2, 3, 7, 64, 20_000] {
+        assert_eq!(
+            list.par_iter().with_threads(threads).map(|x| x * 3),
+            sequential,
+            "{} threads",
+            threads
+        );
+    }
+}
+
+#[test]
+fn for_each_visits_every_element_once() {
+    let list = big();
+    let sum = AtomicUsize::new(0);
+    let count = AtomicUsize::new(0);
+    list.par_iter().with_threads(4).for_each(|&x| {
+        sum.fetch_add(x as usize, Ordering::Relaxed);
+        count.fetch_add(1, Ordering::Relaxed);
+    });
+    assert_eq!(count.into_inner(), list.len());
+    assert_eq!(sum.into_inner() as u64, list.iter().sum::<u64>());
+    assert!(list.par_iter().all(|&x| x < 1000));
+    assert!(!list.par_iter().any(|&x| x >= 1000));
+}
+
+#[test]
+fn any_and_all_stop_early() {
+    let list = big();
+    let calls = AtomicUsize::new(0);
+    let found = list.par_iter().with_threads(1).any(|_| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        true
+    });
+    assert!(found);
+    assert_eq!(calls.swap(0, Ordering::Relaxed), 1);
+
+    let all = list.par_iter().with_threads(1).all(|_| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        false
+    });
+    assert!(!all);
+    assert_eq!(calls.into_inner(), 1);
+}
+
+#[test]
+fn iter_mut_matches_iter_mut() {
+    let mut list = big();
+    let mut expected = list.clone();
+    for x in expected.iter_mut() {
+        *x += 1;
+    }
+    let old = list.par_iter_mut().with_threads(5).map(|x| {
+        *x += 1;
+        *x - 1
+    });
+    assert_eq!(list, expected);
+    assert_eq!(old, big().iter().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn sort() {
+    let mut list = big();
+    let mut expected: Vec<u64> = list.iter().copied().collect();
+    expected.sort();
+    list.par_sort();
+    assert_eq!(Vec::from(list), expected);
+}
+
+#[test]
+fn sort_lengths() {
+    for len in [0, 1, 4096, 4097, 12_345, 50_001] {
+        let mut list: List<u64> = (0..len as u64)
+            .map(|i| i.wrapping_mul(2_654_435_761) % 977)
+            .collect();
+        let mut expected = Vec::from(list.clone());
+        expected.sort();
+        list.par_sort();
+        assert_eq!(Vec::from(list), expected, "{} elements", len);
+    }
+}
+
+#[test]
+fn sort_is_stable() {
+    let mut list: List<(u64, usize)> = big()
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| (x % 10, i))
+        .collect();
+    let mut expected = Vec::from(list.clone());
+    expected.sort_by_key(|&(key, _)| key);
+    list.par_sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(Vec::from(list), expected);
+}
+
+#[test]
+fn sort_keeps_every_element_when_compare_panics() {
+    for len in [100u64, 50_001] {
+        let list: List<u64> = (0..len)
+            .map(|i| i.wrapping_mul(2_654_435_761) % 977)
+            .collect();
+        let mut expected = Vec::from(list.clone());
+        expected.sort();
+        let comparisons = AtomicUsize::new(0);
+        list.clone().par_sort_by(|a, b| {
+            comparisons.fetch_add(1, Ordering::Relaxed);
+            a.cmp(b)
+        });
+        // The last comparison of a parallel sort is in the final merge.
+        let total = comparisons.into_inner();
+        for fail_at in [1, total / 2, total] {
+            let mut list = list.clone();
+            let count = AtomicUsize::new(0);
+            let sorted = panic::catch_unwind(AssertUnwindSafe(|| {
+                list.par_sort_by(|a, b| {
+                    if count.fetch_add(1, Ordering::Relaxed) + 1 == fail_at {
+                        panic!("boom");
+                    }
+                    a.cmp(b)
+                })
+            }));
+            assert!(sorted.is_err());
+            let mut elems = Vec::from(list);
+            elems.sort();
+            assert_eq!(
+                elems, expected,
+                "{} elements, failing comparison {}",
+                len, fail_at
+            );
+        }
+    }
+}
+
+#[test]
+fn empty_and_tiny() {
+    let mut empty: List<i32> = List::new();
+    assert_eq!(empty.par_iter().map(|x| *x), Vec::<i32>::new());
+    empty.par_iter_mut().for_each(|x| *x += 1);
+    empty.par_sort();
+    assert!(empty.is_empty());
+
+    let mut one = dlist![1];
+    one.par_sort();
+    assert_eq!(one, dlist![1]);
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn panics_are_passed_on() {
+    big().par_iter().with_threads(4).for_each(|&x| {
+        if x == 999 {
+            panic!("boom");
+        }
+    });
+}
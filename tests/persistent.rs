@@ -0,0 +1,154 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::thread;
+
+use dreamberd_array::persistent::{ArcPList, PList, RcPList};
+use dreamberd_array::{dlist, List};
+
+mod common;
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn versions_are_independent() {
+    let v1: RcPList<i32> = (1..=3).collect();
+    let v2 = v1.insert(0.5, 42);
+    let (_, v3) = v2.remove(-1.0).unwrap();
+    let (popped, v4) = v3.pop().unwrap();
+
+    assert_eq!(v1.to_string(), "[1, 2, 3]");
+    assert_eq!(v2.to_string(), "[1, 2, 42, 3]");
+    assert_eq!(v3.to_string(), "[2, 42, 3]");
+    assert_eq!(*popped, 3);
+    assert_eq!(v4.to_string(), "[2, 42]");
+    assert_eq!(v4.len(), 2);
+}
+
+#[test]
+fn gets_like_list() {
+    for len in [0, 1, 2, 3, 100] {
+        let list: List<i32> = (0..len).collect();
+        let plist: RcPList<i32> = PList::from(list.clone());
+        common::assert_gets_like(&list, |index| plist.get(index).copied());
+    }
+}
+
+#[test]
+fn edits_leave_the_original_alone() {
+    for len in 0..5 {
+        let list: List<i32> = (0..len).collect();
+        let plist: RcPList<i32> = PList::from(list.clone());
+        for index in common::edge_indexes(list.len()) {
+            let mut expected = list.clone();
+            expected.insert(index, 99);
+            let inserted = plist.insert(index, 99);
+            assert_eq!(
+                List::from(&inserted),
+                expected,
+                "insert({}) into {:?}",
+                index,
+                list
+            );
+
+            let mut expected = list.clone();
+            match (plist.remove(index), expected.remove(index)) {
+                (Some((elem, rest)), Some(removed)) => {
+                    assert_eq!(*elem, removed);
+                    assert_eq!(
+                        List::from(&rest),
+                        expected,
+                        "remove({}) from {:?}",
+                        index,
+                        list
+                    );
+                }
+                (None, None) => {}
+                (got, removed) => panic!(
+                    "remove({}) from {:?}: {:?} instead of {:?}",
+                    index,
+                    list,
+                    got.map(|(elem, _)| *elem),
+                    removed
+                ),
+            }
+            assert_eq!(List::from(&plist), list);
+        }
+    }
+}
+
+#[test]
+fn tails_are_shared() {
+    let base: RcPList<i32> = (1..=3).collect();
+    let pushed = base.push(4);
+    let (_, popped) = pushed.pop().unwrap();
+    let inserted = base.insert(1.0, 9);
+
+    assert!(popped.ptr_eq(&base));
+    assert!(!inserted.ptr_eq(&base));
+    let (_, below) = inserted.pop().unwrap();
+    let (_, below) = below.pop().unwrap();
+    let (_, base_below) = base.pop().unwrap();
+    assert!(below.ptr_eq(&base_below));
+}
+
+#[test]
+fn traits() {
+    let a: RcPList<i32> = PList::from(dlist![1, 2]);
+    let b = PList::new().push(1).push(2);
+
+    assert_eq!(a, b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_eq!(hash_of(&a), hash_of(&dlist![1, 2]));
+    assert_eq!(format!("{:?}", a), "[1, 2]");
+    assert_eq!(a.to_string(), "[1, 2]");
+    assert_eq!(format!("{:#}", a), "-1: 1\n0: 2");
+    assert_eq!(a[-1.0], 1);
+    assert_eq!((&a).into_iter().count(), 2);
+    assert!(RcPList::<i32>::default().is_empty());
+    assert_eq!(RcPList::<i32>::default().get(-1.0), None);
+    assert_ne!(a, a.push(3));
+}
+
+#[test]
+fn formats_like_list() {
+    for len in [0, 1, 2, 3, 17, 1_000] {
+        let list: List<u32> = (0..len).collect();
+        let plist: ArcPList<u32> = PList::from(list.clone());
+        assert_eq!(format!("{:?}", plist), format!("{:?}", list));
+        assert_eq!(plist.to_string(), list.to_string());
+        assert_eq!(format!("{:#}", plist), format!("{:#}", list));
+        assert_eq!(hash_of(&plist), hash_of(&list));
+    }
+}
+
+#[test]
+fn long_chains_drop() {
+    let mut list: ArcPList<u32> = PList::new();
+    for i in 0..1_000_000 {
+        list = list.push(i);
+    }
+    let keep = list.pop().unwrap().1.insert(500_000.0, 0);
+    drop(list);
+    assert_eq!(keep.len(), 1_000_000);
+}
+
+#[test]
+fn arc_across_threads() {
+    let list: ArcPList<i32> = (0..100).collect();
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let list = list.clone();
+            thread::spawn(move || list.push(i).iter().sum::<i32>())
+        })
+        .collect();
+    let sums: Vec<i32> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    assert_eq!(sums, vec![4950, 4951, 4952, 4953]);
+}
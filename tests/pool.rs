@@ -0,0 +1,130 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dreamberd_array::{dlist, List};
+
+/// Counts the allocations of the current thread, so tests running in parallel do not interfere.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let out = f();
+    (out, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn reuses_nodes() {
+    let mut list = List::with_capacity(100);
+    let (_, count) = allocations(|| {
+        for round in 0..10 {
+            for elem in 0..99 {
+                list.push(round * elem);
+            }
+            list.insert(50.5, -1);
+            assert_eq!(list.remove(50.5), Some(-1));
+            list.insert(0.0, -1);
+            while list.pop().is_some() {}
+        }
+    });
+    assert_eq!(count, 0);
+    assert_eq!(list.capacity(), 100);
+
+    let mut plain = List::new();
+    let (_, count) = allocations(|| {
+        for elem in 0..100 {
+            plain.push(elem);
+        }
+    });
+    // One node for every 16 elements.
+    assert_eq!(count, 7);
+}
+
+#[test]
+fn capacity() {
+    let mut list = List::new();
+    assert_eq!(list.capacity(), 0);
+    list.push(1);
+    list.pop();
+    assert_eq!(list.capacity(), 0);
+
+    list.reserve(2);
+    assert_eq!(list.capacity(), 2);
+    list.reserve(1);
+    assert_eq!(list.capacity(), 2);
+
+    // Only as many freed nodes as were reserved are kept.
+    list.extend([1, 2, 3, 4]);
+    assert_eq!(list.capacity(), 4);
+    while list.pop().is_some() {}
+    assert_eq!(list.capacity(), 2);
+
+    list.shrink_to_fit();
+    assert_eq!(list.capacity(), 0);
+
+    // The capacity stays at the highest reservation, also after popping below it.
+    list.reserve(40);
+    list.extend(0..20);
+    list.reserve(30);
+    assert_eq!(list.capacity(), 50);
+    while list.pop().is_some() {}
+    assert_eq!(list.capacity(), 50);
+    list.shrink_to_fit();
+    assert_eq!(list.capacity(), 0);
+}
+
+#[test]
+fn same_as_without_pool() {
+    let mut pooled = List::with_capacity(3);
+    let mut plain = List::new();
+    for list in [&mut pooled, &mut plain] {
+        list.extend([1, 2, 3, 4]);
+        list.insert(0.5, 10);
+        list.remove(-1.0);
+        list.pop();
+        list.push(20);
+        list.insert(-0.5, 30);
+    }
+    assert_eq!(pooled, plain);
+    assert_eq!(pooled, dlist![2, 30, 10, 3, 20]);
+    assert_eq!(pooled.clone(), plain);
+}
+
+#[test]
+fn drops_elements_not_spare_nodes() {
+    let drops = Rc::new(Cell::new(0));
+    struct Counted(Rc<Cell<usize>>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let mut list = List::with_capacity(8);
+    for _ in 0..4 {
+        list.push(Counted(Rc::clone(&drops)));
+    }
+    drop(list.pop());
+    drop(list.remove(-1.0));
+    assert_eq!(drops.get(), 2);
+    drop(list);
+    assert_eq!(drops.get(), 4);
+}
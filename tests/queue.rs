@@ -0,0 +1,297 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashSet;
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use dreamberd_array::dlist;
+use dreamberd_array::queue::{
+    ListQueue, Order, RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError,
+};
+
+#[test]
+fn fifo_and_lifo() {
+    let fifo = ListQueue::new();
+    let lifo = ListQueue::new().with_order(Order::Lifo);
+    for x in 1..=3 {
+        fifo.send(x).unwrap();
+        lifo.send(x).unwrap();
+    }
+    assert_eq!(fifo.len(), 3);
+    assert_eq!(fifo.into_list(), dlist![3, 2, 1]);
+    assert_eq!(lifo.recv(), Ok(3));
+    assert_eq!(lifo.try_recv(), Ok(2));
+    assert_eq!(lifo.recv_timeout(Duration::ZERO), Ok(1));
+    assert_eq!(lifo.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn fifo_while_interleaved() {
+    let queue = ListQueue::new();
+    queue.send(1).unwrap();
+    queue.send(2).unwrap();
+    assert_eq!(queue.recv(), Ok(1));
+    queue.send(3).unwrap();
+    queue.send(4).unwrap();
+    assert_eq!(queue.len(), 3);
+    assert_eq!(
+        format!("{:?}", queue),
+        "ListQueue { list: [4, 3, 2], closed: false, limit: None, order: Fifo }"
+    );
+    assert_eq!(queue.recv(), Ok(2));
+    queue.send(5).unwrap();
+    assert_eq!(queue.into_list(), dlist![5, 4, 3]);
+}
+
+#[test]
+fn limit() {
+    let queue = ListQueue::with_limit(2);
+    assert_eq!(queue.limit(), Some(2));
+    queue.send(1).unwrap();
+    queue.try_send(2).unwrap();
+    assert_eq!(queue.try_send(3), Err(TrySendError::Full(3)));
+    assert_eq!(queue.recv(), Ok(1));
+    queue.try_send(3).unwrap();
+    assert_eq!(
+        format!("{:?}", queue),
+        "ListQueue { list: [3, 2], closed: false, limit: Some(2), order: Fifo }"
+    );
+}
+
+#[test]
+#[should_panic(expected = "queue limit must be at least 1")]
+fn zero_limit() {
+    ListQueue::<i32>::with_limit(0);
+}
+
+#[test]
+fn closed_queue_drains_then_fails() {
+    let queue = ListQueue::new();
+    queue.send("a").unwrap();
+    queue.close();
+    queue.close();
+    assert!(queue.is_closed());
+    assert_eq!(queue.send("b"), Err(SendError("b")));
+    assert_eq!(queue.try_send("c"), Err(TrySendError::Closed("c")));
+    assert_eq!(queue.try_recv(), Ok("a"));
+    assert_eq!(queue.try_recv(), Err(TryRecvError::Closed));
+    assert_eq!(queue.recv(), Err(RecvError));
+    assert_eq!(
+        queue.recv_timeout(Duration::from_secs(60)),
+        Err(RecvTimeoutError::Closed)
+    );
+    assert_eq!(RecvError.to_string(), "receiving on a closed queue");
+}
+
+#[test]
+fn recv_timeout_waits_then_gives_up() {
+    let queue: ListQueue<i32> = ListQueue::new();
+    let start = Instant::now();
+    assert_eq!(
+        queue.recv_timeout(Duration::from_millis(30)),
+        Err(RecvTimeoutError::Timeout)
+    );
+    assert!(start.elapsed() >= Duration::from_millis(30));
+    // A timeout too long for an `Instant` still works.
+    queue.send(7).unwrap();
+    assert_eq!(queue.recv_timeout(Duration::MAX), Ok(7));
+}
+
+#[test]
+fn recv_timeout_wakes_on_send() {
+    let queue = Arc::new(ListQueue::new());
+    let receiver = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || queue.recv_timeout(Duration::from_secs(60)))
+    };
+    thread::sleep(Duration::from_millis(10));
+    queue.send(1).unwrap();
+    assert_eq!(receiver.join().unwrap(), Ok(1));
+}
+
+/// Asserts that none of the threads, which all got past a barrier, returns for a while.
+///
+/// They cannot have finished without blocking, so closing the queue afterwards has to wake them.
+fn assert_still_blocked<R>(threads: &[thread::JoinHandle<R>]) {
+    let deadline = Instant::now() + Duration::from_millis(50);
+    while Instant::now() < deadline {
+        assert!(threads.iter().all(|thread| !thread.is_finished()));
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn close_wakes_blocked_receivers() {
+    let queue: Arc<ListQueue<i32>> = Arc::new(ListQueue::new());
+    let started = Arc::new(Barrier::new(9));
+    let receivers: Vec<_> = (0..8)
+        .map(|i| {
+            let queue = Arc::clone(&queue);
+            let started = Arc::clone(&started);
+            thread::spawn(move || {
+                started.wait();
+                if i % 2 == 0 {
+                    queue.recv().map_err(|_| ())
+                } else {
+                    queue.recv_timeout(Duration::from_secs(60)).map_err(|_| ())
+                }
+            })
+        })
+        .collect();
+    started.wait();
+    assert_still_blocked(&receivers);
+    queue.send(1).unwrap();
+    queue.close();
+    let results: Vec<_> = receivers.into_iter().map(|r| r.join().unwrap()).collect();
+    assert_eq!(results.iter().filter(|r| **r == Ok(1)).count(), 1);
+    assert_eq!(results.iter().filter(|r| r.is_err()).count(), 7);
+}
+
+#[test]
+fn close_wakes_blocked_senders() {
+    let queue = Arc::new(ListQueue::with_limit(1));
+    queue.send(0).unwrap();
+    let started = Arc::new(Barrier::new(9));
+    let senders: Vec<_> = (1..=8)
+        .map(|i| {
+            let queue = Arc::clone(&queue);
+            let started = Arc::clone(&started);
+            thread::spawn(move || {
+                started.wait();
+                queue.send(i)
+            })
+        })
+        .collect();
+    started.wait();
+    assert_still_blocked(&senders);
+    queue.close();
+    let failed: HashSet<i32> = senders
+        .into_iter()
+        .filter_map(|s| s.join().unwrap().err())
+        .map(|SendError(i)| i)
+        .collect();
+    assert_eq!(failed, (1..=8).collect());
+    assert_eq!(queue.recv(), Ok(0));
+}
+
+/// Producers, consumers and a closer all race; every element must end up sent back, received or left over, once.
+fn shutdown_race(order: Order, limit: Option<usize>) {
+    const PRODUCERS: usize = 4;
+    const CONSUMERS: usize = 4;
+    const PER_PRODUCER: usize = 2000;
+
+    for round in 0..5 {
+        let queue = match limit {
+            Some(limit) => ListQueue::with_limit(limit),
+            None => ListQueue::new(),
+        };
+        let queue = Arc::new(queue.with_order(order));
+        let barrier = Arc::new(Barrier::new(PRODUCERS + CONSUMERS + 1));
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let mut rejected = Vec::new();
+                    for i in 0..PER_PRODUCER {
+                        let elem = p * PER_PRODUCER + i;
+                        let result = if i % 3 == 0 {
+                            queue.try_send(elem).map_err(|err| match err {
+                                TrySendError::Full(elem) | TrySendError::Closed(elem) => elem,
+                            })
+                        } else {
+                            queue.send(elem).map_err(|SendError(elem)| elem)
+                        };
+                        if let Err(elem) = result {
+                            rejected.push(elem);
+                        }
+                    }
+                    rejected
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|c| {
+                let queue = Arc::clone(&queue);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let mut received = Vec::new();
+                    loop {
+                        let result = match c % 3 {
+                            0 => queue.recv().map_err(|_| true),
+                            1 => queue
+                                .recv_timeout(Duration::from_millis(1))
+                                .map_err(|err| err == RecvTimeoutError::Closed),
+                            _ => queue.try_recv().map_err(|err| err == TryRecvError::Closed),
+                        };
+                        match result {
+                            Ok(elem) => received.push(elem),
+                            Err(true) => return received,
+                            Err(false) => thread::yield_now(),
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        barrier.wait();
+        thread::sleep(Duration::from_micros(500 * round));
+        queue.close();
+
+        let mut seen = HashSet::new();
+        let all = producers
+            .into_iter()
+            .chain(consumers)
+            .flat_map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>();
+        for elem in all {
+            assert!(seen.insert(elem), "{} seen twice", elem);
+        }
+        // Consumers only stop once the queue is closed and empty.
+        assert!(queue.is_empty());
+        assert_eq!(seen.len(), PRODUCERS * PER_PRODUCER, "round {}", round);
+    }
+}
+
+#[test]
+fn shutdown_race_fifo_unbounded() {
+    shutdown_race(Order::Fifo, None);
+}
+
+#[test]
+fn shutdown_race_fifo_bounded() {
+    shutdown_race(Order::Fifo, Some(4));
+}
+
+#[test]
+fn shutdown_race_lifo_bounded() {
+    shutdown_race(Order::Lifo, Some(1));
+}
+
+#[test]
+fn fifo_per_producer() {
+    let queue = Arc::new(ListQueue::with_limit(8));
+    let producers: Vec<_> = (0..3)
+        .map(|p| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                for i in 0..500 {
+                    queue.send((p, i)).unwrap();
+                }
+            })
+        })
+        .collect();
+    let mut last = [None; 3];
+    for _ in 0..1500 {
+        let (p, i) = queue.recv().unwrap();
+        assert!(last[p].is_none_or(|prev| prev < i));
+        last[p] = Some(i);
+    }
+    for producer in producers {
+        producer.join().unwrap();
+    }
+}
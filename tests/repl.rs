@@ -0,0 +1,97 @@
+#![cfg(feature = "std")]
+
+use std::fs;
+use std::process::Command;
+
+use dreamberd_array::repl::{ReplError, Session};
+use dreamberd_array::syntax::ParseErrorKind;
+use dreamberd_array::{dlist, Value};
+
+fn replay(script: &str) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_dreamberd-array"))
+        .arg(script)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn script_replay() {
+    let output = replay("tests/repl/indexing.txt");
+    assert!(output.status.success());
+    let expected = fs::read_to_string("tests/repl/indexing.out").unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
+
+#[test]
+fn script_error() {
+    let output = replay("tests/repl/error.txt");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.ends_with("> get xs 5\n"));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "tests/repl/error.txt:2: index 5 is out of bounds for xs\n");
+}
+
+#[test]
+fn script_missing() {
+    let output = replay("tests/repl/missing.txt");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn commands() {
+    let mut session = Session::new();
+    session.execute("new xs [1, 2, 3]").unwrap();
+    session.execute("insert xs 0.5 'half'").unwrap();
+    session.execute("push xs [4]").unwrap();
+    session.execute("set xs -1 maybe").unwrap();
+    assert_eq!(
+        session.list("xs"),
+        Some(&dlist![
+            Value::from(dreamberd_array::value::Bool::Maybe),
+            Value::from(2),
+            Value::from("half"),
+            Value::from(3),
+            Value::from(vec![4])
+        ])
+    );
+
+    assert_eq!(session.execute("remove xs 0").unwrap().lines().next(), Some("removed 2"));
+    assert_eq!(session.execute("pop xs").unwrap().lines().next(), Some("popped [4]"));
+    assert_eq!(session.execute("show xs").unwrap(), "xs = [maybe, \"half\", 3]\n  -1: maybe\n  0: \"half\"\n  1: 3");
+    assert_eq!(session.execute("drop xs").unwrap(), "dropped xs");
+    assert_eq!(session.list("xs"), None);
+}
+
+#[test]
+fn show_all() {
+    let mut session = Session::new();
+    assert_eq!(session.execute("show").unwrap(), "");
+    session.execute("new b").unwrap();
+    session.execute("push a 1").unwrap();
+    assert_eq!(session.execute("show").unwrap(), "a = [1]\n  -1: 1\nb = []");
+}
+
+#[test]
+fn errors() {
+    let mut session = Session::new();
+    session.execute("new xs [1]").unwrap();
+
+    assert_eq!(session.execute("frobnicate"), Err(ReplError::UnknownCommand("frobnicate".to_string())));
+    assert_eq!(session.execute("push"), Err(ReplError::MissingArgument("NAME")));
+    assert_eq!(session.execute("push xs"), Err(ReplError::MissingArgument("VALUE")));
+    assert_eq!(session.execute("get xs"), Err(ReplError::MissingArgument("INDEX")));
+    assert_eq!(session.execute("get xs one"), Err(ReplError::InvalidIndex("one".to_string())));
+    assert_eq!(session.execute("get xs NaN"), Err(ReplError::InvalidIndex("NaN".to_string())));
+    assert_eq!(session.execute("get ys 0"), Err(ReplError::UnknownList("ys".to_string())));
+    assert_eq!(session.execute("insert xs 3 1"), Err(ReplError::OutOfBounds("xs".to_string(), 3.0)));
+    assert_eq!(session.execute("remove xs -2"), Err(ReplError::OutOfBounds("xs".to_string(), -2.0)));
+
+    match session.execute("push xs [1,") {
+        Err(ReplError::Parse(err)) => assert_eq!(err.kind, ParseErrorKind::UnexpectedEnd),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    // Failed commands leave the list alone.
+    assert_eq!(session.list("xs"), Some(&dlist![Value::from(1)]));
+}
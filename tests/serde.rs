@@ -0,0 +1,62 @@
+#![cfg(feature = "serde")]
+
+use dreamberd_array::{dlist, List};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Indexed {
+    #[serde(with = "dreamberd_array::serde::indexed")]
+    list: List<String>,
+}
+
+#[test]
+fn sequence() {
+    let list = dlist![1, 2, 3; 0.5 => 42];
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json, "[1,2,42,3]");
+
+    let back: List<usize> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, list);
+}
+
+#[test]
+fn sequence_nested() {
+    let list = dlist![dlist![1], dlist![], dlist![2, 3]];
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json, "[[1],[],[2,3]]");
+
+    let back: List<List<usize>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, list);
+}
+
+#[test]
+fn sequence_rejects_map() {
+    assert!(serde_json::from_str::<List<usize>>("{\"-1\":1}").is_err());
+}
+
+#[test]
+fn indexed_round_trip() {
+    let value = Indexed {
+        list: dlist!["a".to_string(), "b".to_string()],
+    };
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, r#"{"list":{"-1":"a","0":"b"}}"#);
+
+    let back: Indexed = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, value);
+}
+
+#[test]
+fn indexed_fractional_keys() {
+    let json = r#"{"list":{"1":"d","-1":"a","0.5":"c","-0.5":"b"}}"#;
+    let value: Indexed = serde_json::from_str(json).unwrap();
+    assert_eq!(format!("{:#}", value.list), "-1: a\n0: b\n1: c\n2: d");
+}
+
+#[test]
+fn indexed_errors() {
+    assert!(serde_json::from_str::<Indexed>(r#"{"list":{"one":"a"}}"#).is_err());
+    assert!(serde_json::from_str::<Indexed>(r#"{"list":{"NaN":"a"}}"#).is_err());
+    assert!(serde_json::from_str::<Indexed>(r#"{"list":{"0":"a","0.0":"b"}}"#).is_err());
+    assert!(serde_json::from_str::<Indexed>(r#"{"list":["a"]}"#).is_err());
+}
@@ -0,0 +1,291 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+use std::time::Duration;
+
+use dreamberd_array::stream::{
+    async_channel, block_on, SendError, Sender, Sink, Stream, TryRecvError, TrySendError,
+};
+use dreamberd_array::{dlist, List};
+
+/// Counts how often it was woken.
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn counting_waker() -> (Arc<CountingWaker>, Waker) {
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    (Arc::clone(&counter), Waker::from(counter))
+}
+
+/// Counts how often it was woken, after looking at the channel, which deadlocks if it is still locked.
+struct LockingWaker(Sender<i32>, AtomicUsize);
+
+impl Wake for LockingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.is_closed();
+        self.1.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn into_iter_stream_matches_iter() {
+    let list = dlist![1, 2, 3];
+    let expected: Vec<i32> = list.iter().copied().collect();
+    let mut stream = list.into_iter();
+    let streamed = block_on(async {
+        let mut out = Vec::new();
+        while let Some(x) = Stream::next(&mut stream).await {
+            out.push(x);
+        }
+        out
+    });
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn list_sink_pushes() {
+    let mut list = List::new();
+    block_on(async {
+        for x in 1..=3 {
+            list.feed(x).await.unwrap();
+        }
+    });
+    assert_eq!(list, dlist![1, 2, 3]);
+}
+
+#[test]
+fn fifo_order() {
+    let (tx, mut rx) = async_channel(4);
+    for x in 1..=4 {
+        tx.try_send(x).unwrap();
+    }
+    assert_eq!(rx.len(), 4);
+    drop(tx);
+    let received = block_on(async {
+        let mut out = Vec::new();
+        while let Some(x) = rx.recv().await {
+            out.push(x);
+        }
+        out
+    });
+    assert_eq!(received, vec![1, 2, 3, 4]);
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[test]
+fn full_sender_waits_and_is_woken() {
+    let (tx, rx) = async_channel(1);
+    let (counter, waker) = counting_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    tx.try_send(1).unwrap();
+    let mut send = pin!(tx.send(2));
+    assert!(send.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    assert_eq!(send.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(rx.try_recv(), Ok(2));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn one_sender_is_woken_per_free_slot() {
+    let (tx, rx) = async_channel(1);
+    let (first, first_waker) = counting_waker();
+    let (second, second_waker) = counting_waker();
+
+    tx.try_send(1).unwrap();
+    let mut send = Box::pin(tx.send(2));
+    let mut other = pin!(tx.send(3));
+    assert!(send
+        .as_mut()
+        .poll(&mut Context::from_waker(&first_waker))
+        .is_pending());
+    assert!(other
+        .as_mut()
+        .poll(&mut Context::from_waker(&second_waker))
+        .is_pending());
+
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(first.0.load(Ordering::SeqCst), 1);
+    assert_eq!(second.0.load(Ordering::SeqCst), 0);
+
+    // The woken sender gives up, so the slot goes to the next one.
+    drop(send);
+    assert_eq!(second.0.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        other.as_mut().poll(&mut Context::from_waker(&second_waker)),
+        Poll::Ready(Ok(()))
+    );
+    assert_eq!(rx.try_recv(), Ok(3));
+}
+
+#[test]
+fn wakers_run_after_the_lock_is_released() {
+    let (done, finished) = mpsc::channel();
+    thread::spawn(move || {
+        let (tx, mut rx) = async_channel(1);
+        let woken = Arc::new(LockingWaker(tx.clone(), AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&woken));
+        let mut cx = Context::from_waker(&waker);
+
+        // Sending wakes the receiver.
+        assert!(pin!(rx.recv()).poll(&mut cx).is_pending());
+        tx.try_send(1).unwrap();
+        // Receiving wakes a sender waiting for room.
+        let mut send = pin!(tx.send(2));
+        assert!(send.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(rx.try_recv(), Ok(1));
+        assert_eq!(send.as_mut().poll(&mut cx), Poll::Ready(Ok(())));
+        // Dropping the receiver wakes every waiting sender.
+        let mut send = pin!(tx.send(3));
+        assert!(send.as_mut().poll(&mut cx).is_pending());
+        drop(rx);
+        done.send(woken.1.load(Ordering::SeqCst)).unwrap();
+    });
+    assert_eq!(finished.recv_timeout(Duration::from_secs(10)), Ok(3));
+}
+
+#[test]
+fn fifo_order_while_interleaved() {
+    let (tx, rx) = async_channel(32);
+    let mut received = Vec::new();
+    for round in 0..10 {
+        for x in 0..round % 4 + 1 {
+            tx.try_send(round * 10 + x).unwrap();
+        }
+        for _ in 0..round % 3 {
+            if let Ok(x) = rx.try_recv() {
+                received.push(x);
+            }
+        }
+    }
+    while let Ok(x) = rx.try_recv() {
+        received.push(x);
+    }
+    let sent: Vec<_> = (0..10)
+        .flat_map(|round| (0..round % 4 + 1).map(move |x| round * 10 + x))
+        .collect();
+    assert_eq!(received, sent);
+}
+
+#[test]
+fn empty_receiver_waits_and_is_woken() {
+    let (tx, mut rx) = async_channel(1);
+    let (counter, waker) = counting_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut recv = pin!(rx.recv());
+    assert!(recv.as_mut().poll(&mut cx).is_pending());
+    tx.try_send("hi").unwrap();
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    assert_eq!(recv.as_mut().poll(&mut cx), Poll::Ready(Some("hi")));
+}
+
+#[test]
+fn backpressure_across_threads() {
+    const CAPACITY: usize = 3;
+    let (tx, mut rx) = async_channel(CAPACITY);
+    let producers: Vec<_> = (0..4)
+        .map(|p| {
+            let mut tx = tx.clone();
+            thread::spawn(move || {
+                block_on(async {
+                    for i in 0..200 {
+                        if i % 2 == 0 {
+                            tx.send((p, i)).await.unwrap();
+                        } else {
+                            tx.feed((p, i)).await.unwrap();
+                        }
+                    }
+                })
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let received = block_on(async {
+        let mut out = Vec::new();
+        while let Some(item) = rx.next().await {
+            assert!(rx.len() <= CAPACITY);
+            out.push(item);
+            if out.len() % 50 == 0 {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+        out
+    });
+    for producer in producers {
+        producer.join().unwrap();
+    }
+
+    assert_eq!(received.len(), 800);
+    for p in 0..4 {
+        let from_p: Vec<_> = received
+            .iter()
+            .filter(|(q, _)| *q == p)
+            .map(|&(_, i)| i)
+            .collect();
+        assert_eq!(from_p, (0..200).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn closed_channels() {
+    let (tx, rx) = async_channel(2);
+    assert!(!tx.is_closed());
+    assert_eq!(tx.capacity(), 2);
+    drop(rx);
+    assert!(tx.is_closed());
+    assert_eq!(tx.try_send(1), Err(TrySendError::Closed(1)));
+    assert_eq!(block_on(tx.send(2)), Err(SendError(2)));
+    let mut tx = tx;
+    assert_eq!(block_on(tx.feed(3)), Err(SendError(3)));
+    assert_eq!(SendError(3).to_string(), "sending on a closed channel");
+}
+
+#[test]
+fn receiver_dropped_while_sender_waits() {
+    let (tx, rx) = async_channel(1);
+    tx.try_send(1).unwrap();
+    let sender = thread::spawn(move || block_on(tx.send(2)));
+    thread::sleep(Duration::from_millis(20));
+    drop(rx);
+    assert_eq!(sender.join().unwrap(), Err(SendError(2)));
+}
+
+#[test]
+#[should_panic(expected = "capacity must be at least 1")]
+fn zero_capacity() {
+    async_channel::<i32>(0);
+}
+
+#[test]
+fn sink_reserves_a_slot() {
+    let (mut tx, rx) = async_channel(1);
+    let other = tx.clone();
+    let (_, waker) = counting_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(Pin::new(&mut tx).poll_ready(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(other.try_send(1), Err(TrySendError::Full(1)));
+    Pin::new(&mut tx).start_send(2).unwrap();
+    assert_eq!(rx.try_recv(), Ok(2));
+
+    // Dropping a sender gives its reservation back.
+    assert_eq!(Pin::new(&mut tx).poll_ready(&mut cx), Poll::Ready(Ok(())));
+    drop(tx);
+    other.try_send(3).unwrap();
+}
@@ -0,0 +1,226 @@
+#![cfg(feature = "std")]
+
+use dreamberd_array::syntax::{self, Keyword, ParseErrorKind, Terminator, MAX_DEPTH};
+use dreamberd_array::value::Bool;
+use dreamberd_array::{dlist, List, Value};
+
+fn num(n: f64) -> Value {
+    Value::Number(n)
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn numbers() {
+    let list = syntax::parse("[3, 2, 5]").unwrap();
+    assert_eq!(list, dlist![num(3.0), num(2.0), num(5.0)]);
+    assert_eq!(list[-1.0], num(3.0));
+
+    let list = syntax::parse("[-1.5, +2, .5, 1e3, 2.5E-1, 1_000]").unwrap();
+    assert_eq!(
+        list,
+        dlist![num(-1.5), num(2.0), num(0.5), num(1000.0), num(0.25), num(1000.0)]
+    );
+}
+
+#[test]
+fn strings() {
+    let list = syntax::parse(r#"["a", 'b', "it's", 'say "hi"', "tab\tnew\nline\\"]"#).unwrap();
+    assert_eq!(
+        list,
+        dlist![
+            string("a"),
+            string("b"),
+            string("it's"),
+            string("say \"hi\""),
+            string("tab\tnew\nline\\")
+        ]
+    );
+}
+
+#[test]
+fn nested() {
+    let list = syntax::parse("[[], [1, [2]], 'x']").unwrap();
+    assert_eq!(
+        list,
+        dlist![
+            Value::List(List::new()),
+            Value::List(dlist![num(1.0), Value::List(dlist![num(2.0)])]),
+            string("x")
+        ]
+    );
+}
+
+#[test]
+fn words() {
+    let list = syntax::parse("[true, false, maybe, undefined]").unwrap();
+    assert_eq!(
+        list,
+        dlist![
+            Value::Bool(Bool::True),
+            Value::Bool(Bool::False),
+            Value::Bool(Bool::Maybe),
+            Value::Undefined
+        ]
+    );
+    assert_eq!(syntax::to_literal(&list), "[true, false, maybe, undefined]");
+
+    let err = syntax::parse("[1, nope]").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnknownWord("nope".to_string()));
+    assert_eq!((err.line, err.column), (1, 5));
+}
+
+#[test]
+fn whitespace_and_trailing_comma() {
+    let list = syntax::parse("  [\n  1 ,\n  2,\n]  ").unwrap();
+    assert_eq!(list, dlist![num(1.0), num(2.0)]);
+    assert!(syntax::parse("[]").unwrap().is_empty());
+}
+
+#[test]
+fn terminators() {
+    assert!(syntax::parse("[1]!").is_ok());
+    assert!(syntax::parse("[1]!!!").is_ok());
+    assert!(syntax::parse("[1] ?").is_ok());
+
+    let err = syntax::parse("[1]?!").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('!'));
+}
+
+#[test]
+fn values() {
+    assert_eq!(syntax::parse_value("-0.5").unwrap(), num(-0.5));
+    assert_eq!(syntax::parse_value("'a b'").unwrap(), string("a b"));
+    assert_eq!(syntax::parse_value("maybe").unwrap(), Value::Bool(Bool::Maybe));
+    assert_eq!(syntax::parse_value("[[]]").unwrap(), Value::List(dlist![Value::List(List::new())]));
+
+    let err = syntax::parse_value("1 2").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('2'));
+    assert_eq!(syntax::parse_value("").unwrap_err().kind, ParseErrorKind::UnexpectedEnd);
+}
+
+#[test]
+fn declarations() {
+    let decl = syntax::parse_declaration("const const scores = [3, 2, 5]!").unwrap();
+    assert_eq!(decl.binding, Keyword::Const);
+    assert_eq!(decl.contents, Keyword::Const);
+    assert_eq!(decl.name, "scores");
+    assert_eq!(decl.list[1.0], num(5.0));
+    assert_eq!(decl.terminator, Some(Terminator::Bang(1)));
+
+    let decl = syntax::parse_declaration("var var 😀=['x']?").unwrap();
+    assert_eq!(decl.binding, Keyword::Var);
+    assert_eq!(decl.name, "😀");
+    assert_eq!(decl.terminator, Some(Terminator::Question));
+
+    let decl = syntax::parse_declaration("const var xs = [1]").unwrap();
+    assert_eq!(decl.terminator, None);
+}
+
+#[test]
+fn errors() {
+    let err = syntax::parse("[1, 2").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedEnd);
+    assert_eq!((err.line, err.column), (1, 6));
+
+    let err = syntax::parse("[1,\n  @]").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('@'));
+    assert_eq!((err.line, err.column), (2, 3));
+    assert_eq!(err.to_string(), "2:3: unexpected character '@'");
+
+    let err = syntax::parse("[\n'abc]").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnterminatedString);
+    assert_eq!((err.line, err.column), (2, 1));
+
+    let err = syntax::parse(r#"["a\q"]"#).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidEscape('q'));
+    assert_eq!((err.line, err.column), (1, 4));
+
+    let err = syntax::parse("[1.2.3]").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidNumber("1.2.3".to_string()));
+    assert_eq!((err.line, err.column), (1, 2));
+
+    let err = syntax::parse("[1] [2]").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 5));
+
+    let err = syntax::parse_declaration("let const xs = [1]!").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidKeyword("let".to_string()));
+    assert_eq!((err.line, err.column), (1, 1));
+
+    let err = syntax::parse_declaration("const const xs [1]!").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('['));
+}
+
+#[test]
+fn nesting_too_deep() {
+    for levels in [MAX_DEPTH + 1, 10_000, 200_000] {
+        let src = "[".repeat(levels) + &"]".repeat(levels);
+        let err = syntax::parse(&src).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::NestingTooDeep);
+        assert_eq!((err.line, err.column), (1, MAX_DEPTH + 1));
+
+        let err = syntax::parse_value(&format!("\n  {}", src)).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::NestingTooDeep);
+        assert_eq!((err.line, err.column), (2, MAX_DEPTH + 3));
+    }
+    assert_eq!(
+        syntax::parse(&"[".repeat(300)).unwrap_err().to_string(),
+        format!("1:{}: arrays nested more than {} deep", MAX_DEPTH + 1, MAX_DEPTH)
+    );
+
+    let src = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+    let list = syntax::parse(&src).unwrap();
+    let (mut inner, mut depth) = (&list, 1);
+    while let Some(Value::List(next)) = inner.get(-1.0) {
+        inner = next;
+        depth += 1;
+    }
+    assert_eq!(depth, MAX_DEPTH);
+}
+
+#[test]
+fn printing() {
+    assert_eq!(syntax::to_literal(&dlist![3, 2, 5]), "[3, 2, 5]");
+    assert_eq!(syntax::to_literal(&dlist![1.5, -2.0]), "[1.5, -2]");
+    let empty: List<usize> = List::new();
+    assert_eq!(syntax::to_literal(&empty), "[]");
+}
+
+#[test]
+fn printing_strings() {
+    let list = dlist!["plain".to_string(), "a, b]".to_string(), "\"quoted\"\n".to_string()];
+    let printed = syntax::to_literal(&list);
+    assert_eq!(printed, r#"["plain", "a, b]", "\"quoted\"\n"]"#);
+    let parsed = syntax::parse(&printed).unwrap();
+    assert_eq!(parsed, dlist![Value::from("plain"), Value::from("a, b]"), Value::from("\"quoted\"\n")]);
+
+    let nested = dlist![dlist!['x'], dlist![]];
+    assert_eq!(syntax::to_literal(&nested), r#"[["x"], []]"#);
+}
+
+#[test]
+fn non_finite_numbers() {
+    assert!(matches!(
+        syntax::parse("[inf]").unwrap_err().kind,
+        ParseErrorKind::UnknownWord(_)
+    ));
+    for src in ["[-inf]", "[+inf]", "[-NaN]", "[-infinity]", "[1e999]"] {
+        let err = syntax::parse(src).unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::InvalidNumber(_)), "{}", src);
+    }
+    assert_eq!(syntax::parse("[1e-5]").unwrap(), dlist![Value::Number(1e-5)]);
+}
+
+#[test]
+fn round_trip() {
+    let src = r#"[1, -2.5, "quote \" and \\ and \n", [[], ['x']]]"#;
+    let list = syntax::parse(src).unwrap();
+    let printed = syntax::to_literal(&list);
+    assert_eq!(syntax::parse(&printed).unwrap(), list);
+
+    let decl = syntax::parse_declaration("const var xs = [1, 'a']!!!").unwrap();
+    assert_eq!(decl.to_string(), r#"const var xs = [1, "a"]!!!"#);
+    assert_eq!(syntax::parse_declaration(&decl.to_string()).unwrap(), decl);
+}
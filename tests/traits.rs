@@ -0,0 +1,117 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+use dreamberd_array::List;
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn default() {
+    let list: List<usize> = List::default();
+    assert!(list.is_empty());
+}
+
+#[test]
+fn clone() {
+    let mut list = List::new();
+    list.push(1); list.push(2); list.push(3);
+
+    let mut cloned = list.clone();
+    assert_eq!(cloned, list);
+    assert_eq!(cloned[-1.0], 1);
+    assert_eq!(cloned[1.0], 3);
+
+    cloned[0.0] = 42;
+    assert_eq!(list[0.0], 2);
+    assert_ne!(cloned, list);
+}
+
+#[test]
+fn clone_long() {
+    let mut list = List::new();
+    for i in 0..1_000_000 {
+        list.push(i);
+    }
+    let cloned = list.clone();
+    assert_eq!(cloned.len(), 1_000_000);
+    assert_eq!(cloned.peek(), Some(&999_999));
+}
+
+#[test]
+fn eq() {
+    let mut a = List::new();
+    let mut b = List::new();
+    assert_eq!(a, b);
+
+    a.push(1); a.push(2);
+    b.push(1);
+    assert_ne!(a, b);
+
+    b.push(2);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn hash() {
+    let mut a = List::new();
+    a.push(1); a.push(2); a.push(3);
+    let b = a.clone();
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_eq!(hash_of(&a), hash_of(&List::from([1, 2, 3])));
+
+    let mut set = HashSet::new();
+    set.insert(a);
+    assert!(set.contains(&b));
+}
+
+#[test]
+fn ord() {
+    // Index -1 is compared first.
+    let mut a = List::new();
+    a.push(9); a.push(1);
+    let mut b = List::new();
+    b.push(1); b.push(9);
+    assert!(b < a);
+
+    let mut shorter = List::new();
+    shorter.push(9);
+    assert!(shorter < a);
+
+    let mut lists = vec![a.clone(), shorter.clone(), b.clone()];
+    lists.sort();
+    assert_eq!(lists, vec![b, shorter, a]);
+}
+
+#[test]
+fn partial_ord() {
+    let mut a = List::new();
+    a.push(f32::NAN);
+    let mut b = List::new();
+    b.push(1.0);
+    assert_eq!(a.partial_cmp(&b), None);
+}
+
+#[test]
+fn hash_and_ord_long() {
+    let vec: Vec<u32> = (0..10_000).collect();
+    let list = List::from(vec.clone());
+    // Equal lists hash equally, however their elements are split into nodes.
+    let mut reshaped = list.clone();
+    for index in [100.0, 2_000.0, 7_777.0] {
+        let elem = reshaped.remove(index).unwrap();
+        reshaped.insert(index - 0.5, elem);
+    }
+    assert_eq!(reshaped, list);
+    assert_eq!(hash_of(&reshaped), hash_of(&list));
+    assert_eq!(format!("{:?}", list), format!("{:?}", vec));
+
+    let mut bigger = list.clone();
+    bigger[5_000.0] += 1;
+    assert!(list < bigger);
+    assert_eq!(list.cmp(&list.clone()), std::cmp::Ordering::Equal);
+}
@@ -0,0 +1,114 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use dreamberd_array::{dlist, List};
+
+#[test]
+fn commit() {
+    let mut list = dlist![1, 2, 3, 4];
+    let removed = list.transaction(|tx| {
+        let removed = tx.remove(1.5).ok_or(())?;
+        tx.insert(-0.5, 42);
+        tx[0.0] += 1;
+        Ok::<_, ()>(removed)
+    });
+
+    assert_eq!(removed, Ok(4));
+    assert_eq!(list, dlist![1, 43, 2, 3]);
+}
+
+#[test]
+fn rollback_on_err() {
+    let mut list = dlist![1, 2, 3];
+    let result: Result<(), ()> = list.transaction(|tx| {
+        tx.pop();
+        tx.pop();
+        tx.pop();
+        assert!(tx.is_empty());
+        tx.push(7);
+        tx.set(-1.0, 8);
+        tx.remove(-1.0);
+        Err(())
+    });
+
+    assert!(result.is_err());
+    assert_eq!(list, dlist![1, 2, 3]);
+}
+
+#[test]
+fn rollback_on_panic() {
+    let mut list = dlist![1, 2, 3];
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.transaction(|tx| {
+            tx.remove(0.0);
+            tx.insert(-1.0, 0);
+            if tx.len() == 3 {
+                panic!("boom");
+            }
+            Ok::<_, ()>(())
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(list, dlist![1, 2, 3]);
+}
+
+#[test]
+fn owned_elements() {
+    let mut list: List<String> = dlist!["a".to_string(), "b".to_string(), "c".to_string()];
+    let taken = list.transaction(|tx| {
+        let popped: String = tx.pop().ok_or(())?;
+        let removed: String = tx.remove(-1.0).ok_or(())?;
+        let old: String = tx.set(-1.0, popped.clone() + &removed).ok_or(())?;
+        Ok::<_, ()>(vec![popped, removed, old])
+    });
+
+    assert_eq!(taken, Ok(vec!["c".to_string(), "a".to_string(), "b".to_string()]));
+    assert_eq!(list, dlist!["ca".to_string()]);
+
+    let result: Result<(), Box<str>> = list.transaction(|tx| {
+        let popped = tx.pop().ok_or("empty")?;
+        Err(popped.into_boxed_str())
+    });
+    assert_eq!(result, Err("ca".into()));
+    assert_eq!(list, dlist!["ca".to_string()]);
+}
+
+#[test]
+fn without_clone() {
+    #[derive(Debug, PartialEq)]
+    struct Token(u8);
+
+    let mut list: List<Token> = dlist![Token(1), Token(2)];
+    let result: Result<(), ()> = list.transaction(|tx| {
+        tx.push(Token(3));
+        tx.insert(-1.0, Token(0));
+        assert_eq!(tx.get(-1.0), Some(&Token(0)));
+        assert_eq!(tx.len(), 4);
+        Err(())
+    });
+
+    assert!(result.is_err());
+    assert_eq!(list, dlist![Token(1), Token(2)]);
+
+    list.transaction(|tx| {
+        tx.push(Token(3));
+        Ok::<_, ()>(())
+    })
+    .unwrap();
+    assert_eq!(list, dlist![Token(1), Token(2), Token(3)]);
+}
+
+#[test]
+fn ignored_edits() {
+    let mut list: List<i32> = List::new();
+    let result: Result<(), ()> = list.transaction(|tx| {
+        tx.insert(-1.0, 1);
+        assert_eq!(tx.remove(0.0), None);
+        assert!(tx.get_mut(-1.0).is_none());
+        tx.push(1);
+        Err(())
+    });
+
+    assert!(result.is_err());
+    assert!(list.is_empty());
+}
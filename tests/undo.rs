@@ -0,0 +1,123 @@
+use dreamberd_array::dlist;
+use dreamberd_array::undo::UndoableList;
+
+/// Not `Clone`, to check that the structural operations do not need it.
+#[derive(Debug, PartialEq)]
+struct Token(u32);
+
+fn tokens(list: &UndoableList<Token>) -> Vec<u32> {
+    let mut values: Vec<u32> = list.iter().map(|token| token.0).collect();
+    values.reverse();
+    values
+}
+
+#[test]
+fn structural_operations() {
+    let mut list = UndoableList::new();
+    list.push(Token(1));
+    list.push(Token(2));
+    list.push(Token(3));
+    list.insert(-0.5, Token(4));
+    assert_eq!(list.remove(1.0), Some(&Token(2)));
+    assert_eq!(list.pop(), Some(&Token(3)));
+    assert_eq!(list.set(0.0, Token(5)), Some(&Token(4)));
+    assert_eq!(tokens(&list), vec![1, 5]);
+
+    let mut states = Vec::new();
+    while list.undo() {
+        states.push(tokens(&list));
+    }
+    assert_eq!(
+        states,
+        vec![
+            vec![1, 4],
+            vec![1, 4, 3],
+            vec![1, 4, 2, 3],
+            vec![1, 2, 3],
+            vec![1, 2],
+            vec![1],
+            vec![],
+        ]
+    );
+
+    while list.redo() {}
+    assert_eq!(tokens(&list), vec![1, 5]);
+}
+
+#[test]
+fn removing_the_ends() {
+    let mut list = UndoableList::from(dlist![1, 2, 3]);
+    list.remove(1.0);
+    list.remove(-1.0);
+    list.remove(-1.0);
+    assert!(list.is_empty());
+
+    list.undo();
+    assert_eq!(list.as_list(), &dlist![2]);
+    list.undo();
+    assert_eq!(list.as_list(), &dlist![1, 2]);
+    list.undo();
+    assert_eq!(list.as_list(), &dlist![1, 2, 3]);
+}
+
+#[test]
+fn ignored_operations_are_not_recorded() {
+    let mut list: UndoableList<i32> = UndoableList::new();
+    list.insert(-1.0, 1);
+    assert_eq!(list.pop(), None);
+    assert_eq!(list.remove(0.0), None);
+    assert_eq!(list.set(0.0, 1), None);
+    assert!(list.get_mut(0.0).is_none());
+
+    assert!(!list.can_undo());
+}
+
+#[test]
+fn groups() {
+    let mut list = UndoableList::from(dlist![1]);
+    list.begin_group();
+    list.push(2);
+    list[-1.0] = 10;
+    list.end_group();
+    list.begin_group();
+    list.end_group();
+    list.push(3);
+
+    assert_eq!(list.as_list(), &dlist![10, 2, 3]);
+    list.undo();
+    list.undo();
+    assert_eq!(list.as_list(), &dlist![1]);
+    assert!(!list.can_undo());
+
+    list.redo();
+    assert_eq!(list.as_list(), &dlist![10, 2]);
+
+    list.begin_group();
+    list.push(4);
+    assert!(list.can_undo());
+    assert!(!list.can_redo());
+    list.undo();
+    assert_eq!(list.as_list(), &dlist![10, 2]);
+}
+
+#[test]
+fn limit() {
+    let mut list = UndoableList::with_limit(2);
+    for i in 0..5 {
+        list.push(i);
+    }
+    assert!(list.undo());
+    assert!(list.undo());
+    assert!(!list.undo());
+    assert_eq!(list.as_list(), &dlist![0, 1, 2]);
+
+    list.clear_history();
+    assert!(!list.can_redo());
+    assert_eq!(list.into_list(), dlist![0, 1, 2]);
+}
+
+#[test]
+#[should_panic]
+fn zero_limit() {
+    UndoableList::<i32>::with_limit(0);
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use dreamberd_array::value::Bool;
+use dreamberd_array::{dlist, List, Value};
+
+#[test]
+fn from_primitives() {
+    assert_eq!(Value::from(1u8), Value::Number(1.0));
+    assert_eq!(Value::from(-2isize), Value::Number(-2.0));
+    assert_eq!(Value::from(0.5f32), Value::Number(0.5));
+    assert_eq!(Value::from(true), Value::Bool(Bool::True));
+    assert_eq!(Value::from(Bool::Maybe), Value::Bool(Bool::Maybe));
+    assert_eq!(Value::from('c'), Value::String("c".to_string()));
+    assert_eq!(Value::from("str"), Value::String("str".to_string()));
+    assert_eq!(Value::from(()), Value::Undefined);
+    assert_eq!(Value::from(None::<usize>), Value::Undefined);
+    assert_eq!(Value::from(Some(3)), Value::Number(3.0));
+    assert_eq!(Value::default(), Value::Undefined);
+}
+
+#[test]
+fn from_compounds() {
+    // The same shapes as in tests/compounds.rs, now in a single list.
+    let mut map = HashMap::new();
+    map.insert("k".to_string(), 1);
+    let mut tree = BTreeMap::new();
+    tree.insert("a", vec![true]);
+
+    let list: List<Value> = dlist![
+        vec![1, 2, 3].into(),
+        (4, "five", 6.5).into(),
+        [[7], [8]].into(),
+        map.into(),
+        tree.into(),
+        dlist![9, 10].into()
+    ];
+
+    assert_eq!(list.len(), 6);
+    assert_eq!(list[-1.0], Value::List(dlist![1.into(), 2.into(), 3.into()]));
+    assert_eq!(list[0.0].get_path(&[0.0]), Some(&Value::from("five")));
+    assert_eq!(list[1.0].get_path(&[0.0, -1.0]), Some(&Value::from(8)));
+    assert_eq!(list[2.0].as_map().unwrap()["k"], Value::from(1));
+    assert_eq!(list[3.0].to_string(), r#"{"a": [true]}"#);
+    assert_eq!(list[4.0], Value::from(vec![9, 10]));
+}
+
+#[test]
+fn deep_equality() {
+    let a = Value::from(vec![vec![1.0, f64::NAN], vec![]]);
+    let b = Value::from(vec![vec![1.0, f64::NAN], vec![]]);
+    assert_eq!(a, b);
+
+    let c = Value::from(vec![vec![1.0, f64::NAN], vec![0.0]]);
+    assert_ne!(a, c);
+
+    assert_eq!(Value::from(0.0), Value::from(-0.0));
+    assert_ne!(Value::from(1), Value::from("1"));
+    assert_ne!(Value::from(true), Value::Bool(Bool::Maybe));
+    assert_ne!(Value::Undefined, Value::from(vec![Value::Undefined]));
+}
+
+#[test]
+fn get_path() {
+    let mut value = Value::from(vec![Value::from(vec![1, 2]), Value::from("leaf")]);
+
+    assert_eq!(value.get_path(&[-1.0, 0.0]), Some(&Value::from(2)));
+    assert_eq!(value.get_path(&[-1.0, -0.5]), Some(&Value::from(2)));
+    assert_eq!(value.get_path(&[0.0]), Some(&Value::from("leaf")));
+    assert_eq!(value.get_path(&[0.0, -1.0]), None);
+    assert_eq!(value.get_path(&[1.0]), None);
+    assert_eq!(value.get_path(&[-2.0]), None);
+
+    value.get_path_mut(&[-1.0]).unwrap().as_list_mut().unwrap().push(3.into());
+    assert_eq!(value.get_path(&[-1.0, 1.0]), Some(&Value::from(3)));
+}
+
+#[test]
+fn accessors() {
+    assert_eq!(Value::from(2).as_number(), Some(2.0));
+    assert_eq!(Value::from("s").as_str(), Some("s"));
+    assert_eq!(Value::from(false).as_bool(), Some(Bool::False));
+    assert!(Value::Undefined.is_undefined());
+    assert!(Value::from(1).as_list().is_none());
+    assert!(Value::from(1).as_map().is_none());
+}
+
+#[test]
+fn display() {
+    let value = Value::from(vec![
+        Value::from(1.5),
+        Value::from("q\"uote"),
+        Value::Bool(Bool::Maybe),
+        Value::Undefined,
+        Value::from(Vec::<Value>::new()),
+    ]);
+    assert_eq!(value.to_string(), r#"[1.5, "q\"uote", maybe, undefined, []]"#);
+}